@@ -0,0 +1,44 @@
+use hessian_rs::{
+    Fq,
+    Projective,
+    RingElement,
+    TwistedHessianCurve,
+    glv::Glv,
+};
+use rand::{
+    Rng,
+    thread_rng,
+};
+
+/// 2^31 - 1 is a prime ≡ 1 mod 3, and the kernel of the reduction modulo ε is a
+/// subgroup of that order
+const Q: u64 = 2_147_483_647;
+
+fn main() {
+    divan::main();
+}
+
+fn setup() -> (TwistedHessianCurve<Q>, Projective<Q>, Glv<Q>) {
+    let a = RingElement::new(Fq::new(2), Fq::new(7));
+    let curve = TwistedHessianCurve::new(a, RingElement::from_field(Fq::new(0)));
+    let point = curve.kernel_point(Fq::new(1234));
+    let glv = Glv::new(curve, &point, Q).expect("d = 0 and q ≡ 1 mod 3");
+
+    (curve, point, glv)
+}
+
+#[divan::bench]
+fn scalar_mul_double_and_add(bencher: divan::Bencher) {
+    let (curve, point, _) = setup();
+    let scalar = thread_rng().gen_range(Q / 2..Q);
+
+    bencher.bench(|| point.scalar_mul(scalar, curve.a()));
+}
+
+#[divan::bench]
+fn scalar_mul_glv(bencher: divan::Bencher) {
+    let (_, point, glv) = setup();
+    let scalar = thread_rng().gen_range(Q / 2..Q);
+
+    bencher.bench(|| glv.scalar_mul(&point, scalar));
+}
@@ -1,14 +1,19 @@
 use hessian_rs::{
+    FixedBaseTable,
     Fq,
     Projective,
     RingElement,
     TwistedHessianCurve,
 };
 use rand::{
-    Rng,
+    SeedableRng,
+    rngs::StdRng,
     thread_rng,
 };
 
+/// Full-width scalar for comparing scalar multiplication algorithms
+const LARGE_SCALAR: u64 = 0xb7e1_5162_8aed_2a6b;
+
 fn main() {
     divan::main();
 }
@@ -28,32 +33,13 @@ fn bench_projective(moduli: Vec<u64>) {
             }
 
             fn generate_point_{{modulus}}() -> Projective<{{modulus}}> {
-                let curve = create_curve_{{modulus}}();
-
-                let mut rng = thread_rng();
-
-                for _ in 0..100 {
-                    let x_val = rng.gen_range(1..{{modulus}});
-                    let y_val = rng.gen_range(1..{{modulus}});
-                    let z_val = rng.gen_range(1..{{modulus}});
-
-                    let x = RingElement::from_field(Fq::<{{modulus}}>::new(x_val));
-                    let y = RingElement::from_field(Fq::<{{modulus}}>::new(y_val));
-                    let z = RingElement::from_field(Fq::<{{modulus}}>::new(z_val));
-
-                    let point = Projective::new(x, y, z);
-
-                    if curve.contains(&point) {
-                        return point;
-                    }
-                }
+                create_curve_{{modulus}}().random_point(&mut thread_rng())
+            }
 
-                let field_1 = Fq::<{{modulus}}>::new(1);
-                Projective::new(
-                    RingElement::from_field(field_1),
-                    RingElement::from_field(field_1),
-                    RingElement::from_field(field_1)
-                )
+            /// A fixed point, so the fixed-base benches all multiply the same generator
+            fn generator_{{modulus}}() -> Projective<{{modulus}}> {
+                let mut rng = StdRng::seed_from_u64({{modulus}});
+                create_curve_{{modulus}}().random_point(&mut rng)
             }
 
             fn generate_curve_parameter_{{modulus}}() -> RingElement<{{modulus}}> {
@@ -82,17 +68,87 @@ fn bench_projective(moduli: Vec<u64>) {
                 });
             }
 
+            #[divan::bench]
+            fn double_generic_projective_{{modulus}}(bencher: divan::Bencher) {
+                let p = generate_point_{{modulus}}();
+                let a = generate_curve_parameter_{{modulus}}();
+
+                bencher.bench(|| {
+                    p.add(&p, a)
+                });
+            }
+
+            #[divan::bench]
+            fn triple_projective_{{modulus}}(bencher: divan::Bencher) {
+                let p = generate_point_{{modulus}}();
+                let a = generate_curve_parameter_{{modulus}}();
+
+                bencher.bench(|| {
+                    p.triple(a)
+                });
+            }
+
+            #[divan::bench]
+            fn triple_generic_projective_{{modulus}}(bencher: divan::Bencher) {
+                let p = generate_point_{{modulus}}();
+                let a = generate_curve_parameter_{{modulus}}();
+
+                bencher.bench(|| {
+                    p.add(&p, a).add(&p, a)
+                });
+            }
+
             #[divan::bench]
             fn scalar_mul_projective_{{modulus}}(bencher: divan::Bencher) {
                 let p = generate_point_{{modulus}}();
                 let a = generate_curve_parameter_{{modulus}}();
-                let scalar = 2; // TODO: reduce flake / randomness
+                let scalar = 2u64; // TODO: reduce flake / randomness
 
                 bencher.bench(|| {
                     p.scalar_mul(scalar, a)
                 });
             }
 
+            #[divan::bench]
+            fn scalar_mul_large_projective_{{modulus}}(bencher: divan::Bencher) {
+                let p = generate_point_{{modulus}}();
+                let a = generate_curve_parameter_{{modulus}}();
+
+                bencher.bench(|| {
+                    p.scalar_mul(LARGE_SCALAR, a)
+                });
+            }
+
+            #[divan::bench(args = [3, 4, 5])]
+            fn scalar_mul_wnaf_projective_{{modulus}}(bencher: divan::Bencher, width: u32) {
+                let p = generate_point_{{modulus}}();
+                let a = generate_curve_parameter_{{modulus}}();
+
+                bencher.bench(|| {
+                    p.scalar_mul_wnaf(LARGE_SCALAR, width, a)
+                });
+            }
+
+            #[divan::bench]
+            fn scalar_mul_ct_generator_{{modulus}}(bencher: divan::Bencher) {
+                let g = generator_{{modulus}}();
+                let a = generate_curve_parameter_{{modulus}}();
+
+                bencher.bench(|| {
+                    g.scalar_mul_ct(LARGE_SCALAR, u64::MAX, a)
+                });
+            }
+
+            #[divan::bench(args = [2, 4, 6])]
+            fn fixed_base_mul_generator_{{modulus}}(bencher: divan::Bencher, window: u32) {
+                let curve = create_curve_{{modulus}}();
+                let table = FixedBaseTable::new(&curve, &generator_{{modulus}}(), u64::BITS, window);
+
+                bencher.bench(|| {
+                    table.mul(LARGE_SCALAR)
+                });
+            }
+
             #[divan::bench]
             fn negate_projective_{{modulus}}(bencher: divan::Bencher) {
                 let p = generate_point_{{modulus}}();
@@ -0,0 +1,69 @@
+//! Normalised representation of points on a twisted Hessian curve
+
+use crate::{
+    field::Fq,
+    projective::Projective,
+    ring::RingElement,
+};
+
+/// Canonical representative of a point [X:Y:Z] on a twisted Hessian curve over Fq[ε]
+///
+/// Points with invertible Z are scaled to Z = 1. On the curve, Z can only be a non-unit
+/// when Y is invertible, so the remaining points are scaled to Y = 1 instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Affine<const Q: u64> {
+    /// The identity element [0:-1:1]
+    Identity,
+    /// A point [x:y:1] with invertible Z
+    Finite {
+        /// X/Z
+        x: RingElement<Q>,
+        /// Y/Z
+        y: RingElement<Q>,
+    },
+    /// A point [x:1:z] whose Z is not invertible, i.e. Z ≡ 0 modulo ε
+    AtInfinity {
+        /// X/Y
+        x: RingElement<Q>,
+        /// Z/Y, a multiple of ε
+        z: RingElement<Q>,
+    },
+}
+
+impl<const Q: u64> Affine<Q> {
+    /// Check if this is the identity element
+    pub fn is_identity(&self) -> bool {
+        matches!(self, Affine::Identity)
+    }
+
+    /// Convert back to projective coordinates
+    pub fn to_projective(&self) -> Projective<Q> {
+        let one = RingElement::from_field(Fq::new(1));
+
+        match *self {
+            Affine::Identity => Projective::identity(),
+            Affine::Finite { x, y } => Projective::new(x, y, one),
+            Affine::AtInfinity { x, z } => Projective::new(x, one, z),
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::paper_curve_and_generator;
+
+    #[test]
+    fn to_projective__round_trips_through_to_affine() {
+        // P = [1, 2, 3+ε] from the paper
+        let (_, p) = paper_curve_and_generator();
+
+        let affine = p.to_affine();
+        assert!(matches!(affine, Affine::Finite { .. }));
+        assert!(affine.to_projective().is_equal(&p));
+
+        assert!(Projective::<5>::identity().to_affine().is_identity());
+        assert!(Affine::<5>::Identity.to_projective().is_identity());
+    }
+}
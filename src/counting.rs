@@ -0,0 +1,399 @@
+//! Counting the points of twisted Hessian curves
+//!
+//! The residue curve has #E(Fq) = q + 1 - t points with |t| ≤ 2√q by the Hasse bound,
+//! and every point of it has q lifts, so #E(Fq[ε]) = q·#E(Fq).
+//!
+//! Small fields are handled by enumerating the points. Above that, #E(Fq) is found
+//! with Mestre's method on the short Weierstrass model y² = x³ + ax + b and its
+//! quadratic twist y² = x³ + ac²x + bc³, c a non-square, which has 2q + 2 - #E(Fq)
+//! points. Baby-step giant-step finds a multiple of the order of each point in the
+//! Hasse interval, and the orders are accumulated into the group exponent of either
+//! curve until it has a single multiple in the interval. For q > 229 one of the two
+//! exponents always does.
+
+use crate::{
+    curve::TwistedHessianCurve,
+    extension::Fq2,
+    field::Fq,
+    ring::RingElement,
+    weierstrass::{
+        WeierstrassCurve,
+        WeierstrassPoint,
+    },
+};
+use core::ops::{
+    Add,
+    Mul,
+};
+
+/// Largest q for which the points are counted by enumeration
+pub const ENUMERATION_LIMIT: u64 = 256;
+
+/// Bound on q for which the points can be counted, so that baby-step giant-step
+/// covers the Hasse interval with at most [`MAX_BABY_STEPS`] steps
+pub const COUNTING_LIMIT: u64 = 1 << 32;
+
+/// Most baby steps needed for the width 4√q of the Hasse interval with q < 2³²
+const MAX_BABY_STEPS: usize = 1 << 10;
+
+impl<const Q: u64> TwistedHessianCurve<Q> {
+    /// Count the points of the curve over Fq[ε], q·#E(Fq)
+    ///
+    /// This is a u128 as it exceeds 2⁶⁴ for q near 2³². Like
+    /// [`TwistedHessianCurve::residue_order`], it needs q < [`COUNTING_LIMIT`].
+    pub fn order(&self) -> u128 {
+        u128::from(Q)
+            .checked_mul(u128::from(self.residue_order()))
+            .expect("q·#E(Fq) < 2⁶⁶")
+    }
+
+    /// Count the points of the residue curve over Fq
+    ///
+    /// This enumerates the points for q up to [`ENUMERATION_LIMIT`] and uses Mestre's
+    /// method above it, which panics unless q < [`COUNTING_LIMIT`].
+    pub fn residue_order(&self) -> u64 {
+        assert!(Q < COUNTING_LIMIT, "Point counting needs q < 2³²");
+
+        let count = if Q <= ENUMERATION_LIMIT {
+            self.count_by_enumeration()
+        } else {
+            self.count_by_mestre()
+        };
+
+        let (low, high) = hasse_interval::<Q>();
+        assert!(
+            (low..=high).contains(&count),
+            "Point count violates the Hasse bound"
+        );
+
+        count
+    }
+
+    /// Count the points of the residue curve one by one
+    fn count_by_enumeration(&self) -> u64 {
+        let a0 = self.a().constant();
+        let d0 = self.d().constant();
+        let one = Fq::new(1);
+        let mut count = 0u64;
+
+        for x in (0..Q).map(Fq::new) {
+            let ax_cubed = a0.mul(x).mul(x).mul(x);
+
+            // [x:y:1] with a₀x³ + y³ + 1 = d₀xy
+            for y in (0..Q).map(Fq::new) {
+                if ax_cubed.add(y.mul(y).mul(y)).add(one) == d0.mul(x).mul(y) {
+                    count = count.saturating_add(1);
+                }
+            }
+
+            // [x:1:0] with a₀x³ + 1 = 0, as [x:0:0] is never on the curve
+            if ax_cubed.add(one).value() == 0 {
+                count = count.saturating_add(1);
+            }
+        }
+
+        count
+    }
+
+    /// Count the points of the residue curve with Mestre's method
+    fn count_by_mestre(&self) -> u64 {
+        let weierstrass = self.reduce().to_weierstrass();
+        let (a, b) = (weierstrass.a().constant(), weierstrass.b().constant());
+        let c = Fq2::<Q>::NON_RESIDUE;
+        let twist = WeierstrassCurve::new(
+            RingElement::from_field(a.mul(c).mul(c)),
+            RingElement::from_field(b.mul(c).mul(c).mul(c)),
+        );
+
+        let (low, high) = hasse_interval::<Q>();
+        let total = Q.saturating_mul(2).saturating_add(2);
+        let (mut exponent, mut twist_exponent) = (1u64, 1u64);
+
+        for x in (0..Q).map(Fq::new) {
+            exponent = accumulate_exponent(&weierstrass, x, exponent);
+            if let Some(count) = unique_multiple(exponent, low, high) {
+                return count;
+            }
+
+            twist_exponent = accumulate_exponent(&twist, x, twist_exponent);
+            if let Some(count) = unique_multiple(twist_exponent, low, high) {
+                return total.saturating_sub(count);
+            }
+        }
+
+        panic!("Mestre's method needs q > 229");
+    }
+}
+
+/// The Hasse interval [q + 1 - ⌊2√q⌋, q + 1 + ⌊2√q⌋]
+fn hasse_interval<const Q: u64>() -> (u64, u64) {
+    let width = Q.saturating_mul(4).isqrt();
+    let middle = Q.saturating_add(1);
+
+    (middle.saturating_sub(width), middle.saturating_add(width))
+}
+
+/// The only multiple of `exponent` in [low, high], if there is exactly one
+fn unique_multiple(exponent: u64, low: u64, high: u64) -> Option<u64> {
+    let first = low.div_ceil(exponent).saturating_mul(exponent);
+
+    (first <= high && first.saturating_add(exponent) > high).then_some(first)
+}
+
+/// Update the exponent with the order of the point of `curve` at `x`, if there is one
+fn accumulate_exponent<const Q: u64>(
+    curve: &WeierstrassCurve<Q>,
+    x: Fq<Q>,
+    exponent: u64,
+) -> u64 {
+    let (a, b) = (curve.a().constant(), curve.b().constant());
+    let Some(y) = x.mul(x).mul(x).add(a.mul(x)).add(b).sqrt() else {
+        return exponent;
+    };
+    let point = WeierstrassPoint::from_affine(
+        RingElement::from_field(x),
+        RingElement::from_field(y),
+    );
+
+    // the order divides #E, a multiple of the exponent in the Hasse interval
+    let (low, high) = hasse_interval::<Q>();
+    let multiple = point.scalar_mul(exponent, curve);
+    let k = baby_step_giant_step(
+        curve,
+        &multiple,
+        low.div_ceil(exponent),
+        high.checked_div(exponent)
+            .expect("the exponent is non-zero"),
+    );
+    let multiple = exponent.saturating_mul(k);
+    let order = reduce_order(u128::from(multiple), prime_factors(multiple), |m| {
+        point.scalar_mul(m, curve).is_identity()
+    });
+    let order = u64::try_from(order).expect("the order divides a u64");
+
+    let gcd = gcd(exponent, order);
+    exponent
+        .checked_div(gcd)
+        .and_then(|e| e.checked_mul(order))
+        .expect("the exponent divides #E")
+}
+
+/// Find k in [low, high] with k·P = O, given that one exists
+fn baby_step_giant_step<const Q: u64>(
+    curve: &WeierstrassCurve<Q>,
+    point: &WeierstrassPoint<Q>,
+    low: u64,
+    high: u64,
+) -> u64 {
+    let steps = high.saturating_sub(low).isqrt().saturating_add(1);
+    let baby_steps = usize::try_from(steps).expect("at most 2¹⁷ steps");
+    assert!(baby_steps <= MAX_BABY_STEPS, "Too many baby steps");
+
+    // j·P for j < steps, sorted by their affine coordinates
+    let mut table = [((0u64, 0u64), 0u64); MAX_BABY_STEPS];
+    let mut current = WeierstrassPoint::identity();
+    for (j, entry) in (0..steps).zip(table.iter_mut()) {
+        *entry = (key(&current), j);
+        current = current.add(point, curve);
+    }
+    let table = &mut table[..baby_steps];
+    table.sort_unstable();
+
+    // (low + i·steps)·P + j·P = O
+    let giant_step = point.scalar_mul(steps, curve).negate();
+    let mut current = point.scalar_mul(low, curve).negate();
+    let mut base = low;
+    while base <= high {
+        let target = key(&current);
+        if let Ok(index) = table.binary_search_by(|(k, _)| k.cmp(&target)) {
+            return base.saturating_add(table[index].1);
+        }
+
+        current = current.add(&giant_step, curve);
+        base = base.saturating_add(steps);
+    }
+
+    panic!("No multiple of the order in the interval");
+}
+
+/// Affine coordinates as a sort key, the identity getting an x-coordinate out of Fq
+fn key<const Q: u64>(point: &WeierstrassPoint<Q>) -> (u64, u64) {
+    match point.to_affine() {
+        Some((x, y)) => (x.constant().value(), y.constant().value()),
+        None => (Q, 0),
+    }
+}
+
+/// The order of an element, given a multiple of it, the primes dividing that
+/// multiple, and a test for m·P = O
+pub(crate) fn reduce_order(
+    multiple: u128,
+    primes: impl Iterator<Item = u64>,
+    is_identity: impl Fn(u128) -> bool,
+) -> u128 {
+    // divide out each prime p as long as (order/p)·P = O
+    let mut order = multiple;
+    for p in primes.map(u128::from) {
+        while order.checked_rem(p) == Some(0) {
+            let smaller = order.checked_div(p).expect("p is non-zero");
+            if !is_identity(smaller) {
+                break;
+            }
+            order = smaller;
+        }
+    }
+
+    order
+}
+
+/// The distinct primes dividing n, by trial division
+pub(crate) fn prime_factors(n: u64) -> impl Iterator<Item = u64> {
+    let mut rest = n;
+    let mut p = 1u64;
+
+    core::iter::from_fn(move || {
+        while rest > 1 {
+            p = p.saturating_add(1);
+            // past √rest, what remains is prime
+            if p.saturating_mul(p) > rest {
+                p = rest;
+            }
+            if rest.checked_rem(p) == Some(0) {
+                while rest.checked_rem(p) == Some(0) {
+                    rest = rest.checked_div(p).expect("p is non-zero");
+                }
+                return Some(p);
+            }
+        }
+        None
+    })
+}
+
+/// Greatest common divisor
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a.checked_rem(b).expect("b is non-zero"));
+    }
+    a
+}
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        projective::Projective,
+        test_support::paper_curve,
+    };
+
+    #[test]
+    fn order__kats_paper_3_1() {
+        let curve = paper_curve();
+
+        assert_eq!(curve.residue_order(), 9);
+        assert_eq!(curve.order(), 45);
+    }
+
+    #[test]
+    fn count_by_mestre__matches_enumeration() {
+        const Q: u64 = 1009;
+
+        for (a, d) in [(1, 2), (2, 5), (3, 7), (5, 0), (7, 11), (1, 1000)] {
+            let curve = TwistedHessianCurve::<Q>::new(
+                RingElement::from_field(Fq::new(a)),
+                RingElement::from_field(Fq::new(d)),
+            );
+
+            assert_eq!(curve.count_by_mestre(), curve.count_by_enumeration());
+        }
+    }
+
+    #[test]
+    fn order__kills_points_on_large_curves() {
+        const Q: u64 = 2_147_483_647;
+
+        let a = RingElement::new(Fq::new(2), Fq::new(7));
+        let d = RingElement::new(Fq::new(5), Fq::new(3));
+        let curve = TwistedHessianCurve::<Q>::new(a, d);
+        let residue = curve.reduce();
+        let order = curve.residue_order();
+
+        let (low, high) = hasse_interval::<Q>();
+        assert!((low..=high).contains(&order));
+
+        let weierstrass = residue.to_weierstrass();
+        for x in (1..200).map(Fq::new) {
+            if let Some(y) = x
+                .mul(x)
+                .mul(x)
+                .add(weierstrass.a().constant().mul(x))
+                .add(weierstrass.b().constant())
+                .sqrt()
+            {
+                let point = Projective::from_weierstrass(
+                    &WeierstrassPoint::from_affine(
+                        RingElement::from_field(x),
+                        RingElement::from_field(y),
+                    ),
+                    &residue,
+                );
+                assert!(residue.scalar_mul(&point, order).is_identity());
+                let lift = curve.lift_point(&point, x);
+                assert!(curve.scalar_mul(&lift, curve.order()).is_identity());
+
+                let point_order = curve.point_order(&lift);
+                assert_eq!(curve.order().checked_rem(u128::from(point_order)), Some(0));
+                assert!(curve.scalar_mul(&lift, point_order).is_identity());
+            }
+        }
+    }
+
+    #[test]
+    fn order__exceeds_u64_near_2_32() {
+        const Q: u64 = 4_294_967_291;
+
+        // q·#E(Fq) > 2⁶⁴ as soon as #E(Fq) > q + 10
+        let curve = (2..)
+            .map(|a| {
+                TwistedHessianCurve::<Q>::new(
+                    RingElement::new(Fq::new(a), Fq::new(1)),
+                    RingElement::new(Fq::new(3), Fq::new(2)),
+                )
+            })
+            .find(|curve| curve.residue_order() > Q.saturating_add(10))
+            .unwrap();
+        let order = curve.order();
+        assert!(order > u128::from(u64::MAX));
+        assert_eq!(
+            order,
+            u128::from(Q).saturating_mul(u128::from(curve.residue_order()))
+        );
+
+        let residue = curve.reduce();
+        let weierstrass = residue.to_weierstrass();
+        let (a, b) = (weierstrass.a().constant(), weierstrass.b().constant());
+        let (x, y) = (1..Q)
+            .map(Fq::new)
+            .find_map(|x| Some((x, x.mul(x).mul(x).add(a.mul(x)).add(b).sqrt()?)))
+            .unwrap();
+        let point = Projective::from_weierstrass(
+            &WeierstrassPoint::from_affine(
+                RingElement::from_field(x),
+                RingElement::from_field(y),
+            ),
+            &residue,
+        );
+        let lift = curve.lift_point(&point, Fq::new(1));
+
+        assert!(curve.scalar_mul(&lift, order).is_identity());
+        let point_order = curve.point_order_u128(&lift);
+        assert_eq!(order.checked_rem(point_order), Some(0));
+        assert!(curve.scalar_mul(&lift, point_order).is_identity());
+    }
+
+    #[test]
+    fn hasse_interval__bounds() {
+        assert_eq!(hasse_interval::<5>(), (2, 10));
+        assert_eq!(hasse_interval::<1009>(), (947, 1073));
+    }
+}
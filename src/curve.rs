@@ -1,9 +1,17 @@
 //! Twisted hessian curve over the ring Fq[ε]
 
 use crate::{
+    counting::{
+        prime_factors,
+        reduce_order,
+    },
     field::Fq,
-    projective::Projective,
+    projective::{
+        Projective,
+        ScalarMulMethod,
+    },
     ring::RingElement,
+    scalar::Scalar,
 };
 use core::ops::{
     Mul,
@@ -11,7 +19,7 @@ use core::ops::{
 };
 
 /// Represents a twisted Hessian curve aX³ + Y³ + Z³ = dXYZ over the ring Fq[ε]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct TwistedHessianCurve<const Q: u64> {
     a: RingElement<Q>,
     d: RingElement<Q>,
@@ -68,42 +76,82 @@ impl<const Q: u64> TwistedHessianCurve<Q> {
     }
 
     /// Multiply a point by a scalar
-    pub fn scalar_mul(&self, p: &Projective<Q>, scalar: u64) -> Projective<Q> {
+    pub fn scalar_mul(
+        &self,
+        p: &Projective<Q>,
+        scalar: impl Into<Scalar>,
+    ) -> Projective<Q> {
         assert!(self.contains(p), "Projective must be on the curve");
 
         p.scalar_mul(scalar, self.a)
     }
 
+    /// Multiply a point by a scalar with the chosen algorithm
+    pub fn scalar_mul_with(
+        &self,
+        p: &Projective<Q>,
+        scalar: impl Into<Scalar>,
+        method: ScalarMulMethod,
+    ) -> Projective<Q> {
+        assert!(self.contains(p), "Projective must be on the curve");
+
+        p.scalar_mul_with(scalar, method, self.a)
+    }
+
+    /// Multiply a point by a scalar in constant time, see [`Projective::scalar_mul_ct`]
+    pub fn scalar_mul_ct(
+        &self,
+        p: &Projective<Q>,
+        scalar: impl Into<Scalar>,
+        order: impl Into<Scalar>,
+    ) -> Projective<Q> {
+        assert!(self.contains(p), "Projective must be on the curve");
+
+        p.scalar_mul_ct(scalar, order, self.a)
+    }
+
+    /// Check if a point is on the curve and killed by `order`, i.e. lies in the
+    /// subgroup of that order when it is the order of a cyclic subgroup
+    pub fn is_in_subgroup(
+        &self,
+        point: &Projective<Q>,
+        order: impl Into<Scalar>,
+    ) -> bool {
+        self.contains(point) && self.scalar_mul(point, order).is_identity()
+    }
+
+    /// Multiply a point by the cofactor, mapping any point of the curve into the
+    /// subgroup of index `cofactor`
+    pub fn clear_cofactor(
+        &self,
+        point: &Projective<Q>,
+        cofactor: impl Into<Scalar>,
+    ) -> Projective<Q> {
+        self.scalar_mul(point, cofactor)
+    }
+
     /// Calculate the order of a point (the smallest positive k such that k*P = O)
+    ///
+    /// This panics when the order does not fit a u64, which can only happen for q near
+    /// 2³², see [`TwistedHessianCurve::point_order_u128`].
     pub fn point_order(&self, point: &Projective<Q>) -> u64 {
-        // TODO: optimize this, rlc
-        assert!(self.contains(point), "Projective must be on the curve");
-
-        let identity = self.identity();
+        u64::try_from(self.point_order_u128(point))
+            .expect("The order of the point must fit a u64")
+    }
 
-        // handle the case where the point is already the identity
-        if point.is_equal(&identity) {
-            return 1;
-        }
+    /// Calculate the order of a point as a u128, which holds any order for q < 2³²
+    ///
+    /// The order divides [`TwistedHessianCurve::order`] = q·#E(Fq), from which the
+    /// prime factors that still kill the point are divided out.
+    pub fn point_order_u128(&self, point: &Projective<Q>) -> u128 {
+        assert!(self.contains(point), "Projective must be on the curve");
 
-        // for a curve over F_q[ε], the group order is <= q^2
-        let max_possible_order = Q.pow(2);
-
-        for order in 2..=max_possible_order {
-            let multiple = self.scalar_mul(point, order);
-            if multiple.is_equal(&identity) {
-                let previous = self.scalar_mul(point, order - 1);
-                if !previous.is_equal(&identity) {
-                    return order;
-                }
-            }
-        }
+        let residue_order = self.residue_order();
+        let primes = prime_factors(residue_order).chain(core::iter::once(Q));
 
-        // this should never happen for a valid point on the curve
-        panic!(
-            "Could not determine the order of the point within range 1..{}",
-            max_possible_order
-        );
+        reduce_order(self.order(), primes, |multiple| {
+            self.scalar_mul(point, multiple).is_identity()
+        })
     }
 }
 
@@ -115,6 +163,7 @@ mod tests {
     use crate::{
         field::Fq,
         ring::RingElement,
+        test_support::paper_curve_and_generator,
     };
 
     #[test]
@@ -132,6 +181,20 @@ mod tests {
         assert_eq!(curve.modulus(), 5);
     }
 
+    #[test]
+    fn clear_cofactor__maps_into_the_subgroup() {
+        // P = [1:2:3+ε] has order 45, and 5P generates the subgroup of order 9
+        let (curve, p) = paper_curve_and_generator();
+        let d = curve.d();
+
+        for k in 0..45u64 {
+            let point = curve.scalar_mul(&p, k);
+            assert_eq!(curve.is_in_subgroup(&point, 9u64), k % 5 == 0);
+            assert!(curve.is_in_subgroup(&curve.clear_cofactor(&point, 5u64), 9u64));
+        }
+        assert!(!curve.is_in_subgroup(&Projective::new(d, d, d), 9u64));
+    }
+
     #[test]
     #[should_panic(expected = "a*(27a-d³) must be invertible for a valid curve")]
     fn new__should_fail__when__parameters_are_invalid() {
@@ -1,14 +1,37 @@
 //! ECDH
+#[cfg(feature = "rand")]
+use crate::elligator::{
+    Elligator,
+    REPRESENTATIVE_SIZE,
+};
+#[cfg(feature = "alloc")]
+use crate::fixed_base::FixedBaseTable;
+#[cfg(feature = "rand")]
+use crate::scalar::LIMBS;
 use crate::{
+    counting::prime_factors,
     curve::TwistedHessianCurve,
     projective::Projective,
+    scalar::Scalar,
 };
+#[cfg(feature = "rand")]
+use rand_core::RngCore;
+
+/// Window width of the generator table built by [`DiffieHellman::new`]
+#[cfg(feature = "alloc")]
+const FIXED_BASE_WINDOW: u32 = 4;
 
 /// ECDH for a twisted hessian curve w/ ring
+///
+/// Private keys only ever go through constant-time scalar multiplication: a
+/// precomputed generator table for key generation (with `alloc`) and the Montgomery
+/// ladder otherwise.
 pub struct DiffieHellman<const Q: u64> {
     curve: TwistedHessianCurve<Q>,
     generator: Projective<Q>,
-    order: u64,
+    order: Scalar,
+    #[cfg(feature = "alloc")]
+    generator_table: FixedBaseTable<Q>,
 }
 
 impl<const Q: u64> DiffieHellman<Q> {
@@ -16,9 +39,11 @@ impl<const Q: u64> DiffieHellman<Q> {
     pub fn new(
         curve: TwistedHessianCurve<Q>,
         generator: Projective<Q>,
-        order: u64,
+        order: impl Into<Scalar>,
     ) -> Self {
+        let order = order.into();
         assert!(curve.contains(&generator), "Generator must be on the curve");
+        assert!(!order.is_zero(), "order must be non-zero");
 
         // Verify the order is correct
         let identity = curve.identity();
@@ -32,41 +57,188 @@ impl<const Q: u64> DiffieHellman<Q> {
             curve,
             generator,
             order,
+            #[cfg(feature = "alloc")]
+            generator_table: FixedBaseTable::new(
+                &curve,
+                &generator,
+                order.bits(),
+                FIXED_BASE_WINDOW,
+            ),
         }
     }
 
+    /// Get the curve
+    pub fn curve(&self) -> &TwistedHessianCurve<Q> {
+        &self.curve
+    }
+
+    /// Get the generator point
+    pub fn generator(&self) -> Projective<Q> {
+        self.generator
+    }
+
+    /// Get the order of the generator
+    pub fn order(&self) -> Scalar {
+        self.order
+    }
+
     /// Generate a new key pair (private key, public key)
-    pub fn generate_keypair(&self, private_key: u64) -> (u64, Projective<Q>) {
+    pub fn generate_keypair(
+        &self,
+        private_key: impl Into<Scalar>,
+    ) -> (Scalar, Projective<Q>) {
         // Ensure private key is within the valid range
-        let private_key = private_key % self.order;
-        if private_key == 0 {
+        let private_key = private_key.into().rem(&self.order);
+        if private_key.is_zero() {
             panic!("Private key cannot be zero");
         }
 
-        let public_key = self.curve.scalar_mul(&self.generator, private_key);
+        #[cfg(feature = "alloc")]
+        let public_key = self.generator_table.mul(private_key);
+        #[cfg(not(feature = "alloc"))]
+        let public_key =
+            self.curve
+                .scalar_mul_ct(&self.generator, private_key, self.order);
+
         (private_key, public_key)
     }
 
+    /// Generate a random key pair whose public key has an Elligator representative,
+    /// returning it with its encoding as a uniformly random string
+    ///
+    /// Private keys are drawn uniformly below the order until the public key is
+    /// encodable, about twice on average. Since uniform strings decode to uniform
+    /// points of the whole curve, the strings are only indistinguishable from random
+    /// ones when the generator's subgroup is the whole group, so this returns `None`
+    /// unless the order is #E(Fq[ε]).
+    #[cfg(feature = "rand")]
+    pub fn generate_encodable_keypair(
+        &self,
+        elligator: &Elligator<Q>,
+        rng: &mut impl RngCore,
+    ) -> Option<(Scalar, Projective<Q>, [u8; REPRESENTATIVE_SIZE])> {
+        assert!(
+            elligator.curve() == self.curve,
+            "Elligator map must be for the same curve"
+        );
+        if self.order != Scalar::from(self.curve.order()) {
+            return None;
+        }
+
+        loop {
+            let private_key = self.random_private_key(rng);
+            let (private_key, public_key) = self.generate_keypair(private_key);
+
+            if let Some(encoded) = elligator.encode(&public_key, rng) {
+                return Some((private_key, public_key, encoded));
+            }
+        }
+    }
+
+    /// Draw a uniform non-zero scalar below the order by rejection sampling
+    #[cfg(feature = "rand")]
+    fn random_private_key(&self, rng: &mut impl RngCore) -> Scalar {
+        let bits = self.order.bits();
+
+        loop {
+            let mut limbs = [0u64; LIMBS];
+            for (i, limb) in (0u32..).zip(limbs.iter_mut()) {
+                // keep the bits below the bit length of the order
+                let low = i.saturating_mul(64);
+                let kept = bits.saturating_sub(low).min(64);
+                let mask = u64::MAX
+                    .checked_shr(64u32.saturating_sub(kept))
+                    .unwrap_or(0);
+                *limb = rng.next_u64() & mask;
+            }
+
+            let candidate = Scalar::from_limbs(limbs);
+            if !candidate.is_zero() && candidate < self.order {
+                return candidate;
+            }
+        }
+    }
+
     /// Compute the shared secret from a private key and another party's public key
+    ///
+    /// The public key must lie in the generator's subgroup. Use [`PublicKey::new`] to
+    /// also reject the identity and points of lower order.
     pub fn compute_shared_secret(
         &self,
-        private_key: u64,
+        private_key: impl Into<Scalar>,
         public_key: &Projective<Q>,
     ) -> Projective<Q> {
         assert!(
             self.curve.contains(public_key),
             "Public key must be on the curve"
         );
-        self.curve.scalar_mul(public_key, private_key)
+        assert!(
+            self.curve.is_in_subgroup(public_key, self.order),
+            "Public key must be in the generator's subgroup"
+        );
+        let private_key = private_key.into().rem(&self.order);
+        self.curve
+            .scalar_mul_ct(public_key, private_key, self.order)
+    }
+
+    /// Compute the shared secret from a private key and a validated public key
+    pub fn shared_secret(
+        &self,
+        private_key: impl Into<Scalar>,
+        public_key: &PublicKey<Q>,
+    ) -> Projective<Q> {
+        let private_key = private_key.into().rem(&self.order);
+        self.curve
+            .scalar_mul_ct(&public_key.point, private_key, self.order)
+    }
+}
+
+/// Public key of a Diffie-Hellman exchange, checked to generate the whole subgroup
+/// of the generator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicKey<const Q: u64> {
+    point: Projective<Q>,
+}
+
+impl<const Q: u64> PublicKey<Q> {
+    /// Validate another party's public key
+    ///
+    /// Returns `None` for points off the curve, outside the generator's subgroup, the
+    /// identity, and any point whose order is a proper divisor of the generator's
+    /// order, since those leak the private key modulo their order. The latter factors
+    /// the order by trial division, so every point is rejected when the order is wider
+    /// than 64 bits.
+    pub fn new(dh: &DiffieHellman<Q>, point: Projective<Q>) -> Option<Self> {
+        let curve = dh.curve();
+        if !curve.is_in_subgroup(&point, dh.order) || point.is_identity() {
+            return None;
+        }
+
+        // P has full order n iff (n/p)·P ≠ O for every prime p dividing n
+        let order = dh.order.to_u64()?;
+        for p in prime_factors(order) {
+            let cofactor = order.checked_div(p).expect("p is non-zero");
+            if curve.scalar_mul(&point, cofactor).is_identity() {
+                return None;
+            }
+        }
+
+        Some(PublicKey { point })
+    }
+
+    /// Get the point
+    pub fn point(&self) -> Projective<Q> {
+        self.point
     }
 }
 
 /// Simulates a Diffie-Hellman key exchange between two parties
 pub fn simulate_key_exchange<const Q: u64>(
     dh: &DiffieHellman<Q>,
-    alice_private: u64,
-    bob_private: u64,
+    alice_private: impl Into<Scalar>,
+    bob_private: impl Into<Scalar>,
 ) -> (Projective<Q>, Projective<Q>) {
+    let (alice_private, bob_private) = (alice_private.into(), bob_private.into());
     let (_, alice_public) = dh.generate_keypair(alice_private);
     let (_, bob_public) = dh.generate_keypair(bob_private);
 
@@ -83,6 +255,7 @@ mod tests {
     use crate::{
         field::Fq,
         ring::RingElement,
+        test_support::paper_curve_and_generator,
     };
 
     #[test]
@@ -113,12 +286,12 @@ mod tests {
         );
 
         // the paper says this point has order 45
-        let order = 45;
+        let order = 45u64;
 
         let dh = DiffieHellman::new(curve, generator, order);
 
-        let alice_private = 4;
-        let bob_private = 35;
+        let alice_private = 4u64;
+        let bob_private = 35u64;
 
         // simulate key exchange
         let (alice_shared, bob_shared) =
@@ -141,4 +314,155 @@ mod tests {
             "Shared secret should equal expected value from paper"
         );
     }
+
+    fn paper_dh() -> DiffieHellman<5> {
+        let (curve, generator) = paper_curve_and_generator();
+
+        DiffieHellman::new(curve, generator, 45u64)
+    }
+
+    #[test]
+    fn public_key__accepts_exactly_the_points_of_full_order() {
+        let dh = paper_dh();
+        let g = dh.generator();
+
+        // kP has order 45 iff gcd(k, 45) = 1
+        for k in 0..45u64 {
+            let point = dh.curve().scalar_mul(&g, k);
+            let full_order = k % 3 != 0 && k % 5 != 0;
+            assert_eq!(PublicKey::new(&dh, point).is_some(), full_order);
+        }
+
+        // Alice's key from Section 3.1.2 is valid, Bob's 35P only has order 9
+        let (_, alice_public) = dh.generate_keypair(4u64);
+        let (_, bob_public) = dh.generate_keypair(35u64);
+        let alice_key = PublicKey::new(&dh, alice_public).unwrap();
+        assert!(PublicKey::new(&dh, bob_public).is_none());
+        assert_eq!(
+            dh.shared_secret(35u64, &alice_key),
+            dh.compute_shared_secret(35u64, &alice_public)
+        );
+    }
+
+    #[test]
+    fn public_key__should_reject_points_outside_the_subgroup() {
+        let paper = paper_dh();
+        let curve = *paper.curve();
+        let p = paper.generator();
+
+        // 5P generates the subgroup of order 9, which P is not in
+        let dh = DiffieHellman::new(curve, curve.scalar_mul(&p, 5u64), 9u64);
+        assert!(PublicKey::new(&dh, p).is_none());
+        assert!(PublicKey::new(&dh, curve.identity()).is_none());
+        assert!(PublicKey::new(&dh, curve.clear_cofactor(&p, 5u64)).is_some());
+
+        let one = RingElement::from_field(Fq::new(1));
+        assert!(PublicKey::new(&dh, Projective::new(one, one, one)).is_none());
+    }
+
+    #[test]
+    fn public_key__rejects_every_point_when_the_order_cannot_be_factored() {
+        let paper = paper_dh();
+        let p = paper.generator();
+
+        // 45·2⁶⁴ kills P, but is too wide for trial division
+        let dh = DiffieHellman::new(*paper.curve(), p, 45u128 << 64);
+        assert!(PublicKey::new(&dh, p).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Public key must be in the generator's subgroup")]
+    fn compute_shared_secret__should_panic_when_key_is_outside_the_subgroup() {
+        let paper = paper_dh();
+        let curve = *paper.curve();
+        let p = paper.generator();
+
+        let dh = DiffieHellman::new(curve, curve.scalar_mul(&p, 5u64), 9u64);
+        dh.compute_shared_secret(2u64, &p);
+    }
+
+    /// Check that a point has order exactly n = 2²·3·7 or 2·3·7
+    #[cfg(feature = "rand")]
+    fn has_order(curve: &TwistedHessianCurve<7>, point: &Projective<7>, n: u64) -> bool {
+        curve.scalar_mul(point, n).is_identity()
+            && [2, 3, 7].iter().all(|p| {
+                !curve
+                    .scalar_mul(point, n.checked_div(*p).expect("p is non-zero"))
+                    .is_identity()
+            })
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn generate_encodable_keypair__gives_decodable_public_keys() {
+        use rand::{
+            SeedableRng,
+            rngs::StdRng,
+        };
+
+        type F7 = Fq<7>;
+
+        // the residue curve has 12 points and a single one of order 2, so the 84
+        // points of the curve form a cyclic group
+        let a = RingElement::new(F7::new(3), F7::new(1)); // 3+ε
+        let d = RingElement::new(F7::new(1), F7::new(3)); // 1+3ε
+        let curve = TwistedHessianCurve::new(a, d);
+        let elligator = Elligator::new(curve).unwrap();
+        let mut rng = StdRng::seed_from_u64(49);
+
+        let generator = core::iter::repeat_with(|| curve.random_point(&mut rng))
+            .find(|p| has_order(&curve, p, 84))
+            .unwrap();
+        let dh = DiffieHellman::new(curve, generator, 84u64);
+
+        for _ in 0..20 {
+            let (private_key, public_key, encoded) =
+                dh.generate_encodable_keypair(&elligator, &mut rng).unwrap();
+            assert!(!private_key.is_zero() && private_key < dh.order());
+            assert_eq!(public_key, curve.scalar_mul(&generator, private_key));
+            assert_eq!(elligator.decode(&encoded), Some(public_key));
+        }
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn generate_encodable_keypair__rejects_proper_subgroups() {
+        use rand::{
+            SeedableRng,
+            rngs::StdRng,
+        };
+
+        type F7 = Fq<7>;
+
+        // the residue curve 2X³ + Y³ + Z³ = 0 has 3 points of order 2, so no point
+        // generates all 84 points of the curve
+        let a = RingElement::new(F7::new(2), F7::new(1)); // 2+ε
+        let d = RingElement::new(F7::new(0), F7::new(3)); // 3ε
+        let curve = TwistedHessianCurve::new(a, d);
+        let elligator = Elligator::new(curve).unwrap();
+        let mut rng = StdRng::seed_from_u64(49);
+
+        let generator = core::iter::repeat_with(|| curve.random_point(&mut rng))
+            .find(|p| has_order(&curve, p, 42))
+            .unwrap();
+        let dh = DiffieHellman::new(curve, generator, 42u64);
+        assert!(
+            dh.generate_encodable_keypair(&elligator, &mut rng)
+                .is_none()
+        );
+
+        // a proper subgroup of a cyclic curve
+        let a = RingElement::new(F7::new(3), F7::new(1)); // 3+ε
+        let d = RingElement::new(F7::new(1), F7::new(3)); // 1+3ε
+        let curve = TwistedHessianCurve::new(a, d);
+        let elligator = Elligator::new(curve).unwrap();
+        let generator = core::iter::repeat_with(|| curve.random_point(&mut rng))
+            .find(|p| has_order(&curve, p, 84))
+            .unwrap();
+        let dh = DiffieHellman::new(curve, curve.scalar_mul(&generator, 2u64), 42u64);
+        assert!(
+            dh.generate_encodable_keypair(&elligator, &mut rng)
+                .is_none()
+        );
+    }
 }
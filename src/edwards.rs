@@ -0,0 +1,466 @@
+//! Montgomery and twisted Edwards models of twisted Hessian curves
+//!
+//! The short Weierstrass model y² = x³ + Ax + B of [`TwistedHessianCurve::to_weierstrass`]
+//! has a Montgomery model whenever it has a point (α, 0) of order 2 with 3α² + A = s²
+//! a square. Then u = (x - α)/s and v = y/s give
+//!
+//! B_M v² = u³ + A_M u² + u, A_M = 3α/s, B_M = 1/s,
+//!
+//! which in projective coordinates is the linear map (X : Y : Z) ↦ (X - αZ : Y : sZ),
+//! a group isomorphism without exceptional points. The Montgomery curve is birational
+//! to the twisted Edwards curve a_E x² + y² = 1 + d_E x²y² with a_E = (A_M + 2)/B_M
+//! and d_E = (A_M - 2)/B_M through (x, y) = (u/v, (u - 1)/(u + 1)) (Bernstein,
+//! Birkner, Joye, Lange & Peters, 2008, Theorem 3.2). The identity goes to (0, 1) and
+//! (0, 0) to (0, -1); the other points with v or u + 1 not invertible are exceptional.
+//!
+//! Over Fq[ε], α is the lift of a root modulo ε, which is simple as the curve is
+//! smooth, so 3α² + A is a unit and has a square root exactly when its constant part
+//! is a square in Fq.
+
+use crate::{
+    curve::TwistedHessianCurve,
+    field::Fq,
+    poly::Polynomial,
+    projective::Projective,
+    ring::RingElement,
+    weierstrass::{
+        WeierstrassPoint,
+        sqrt,
+    },
+};
+use alloc::vec;
+use core::ops::{
+    Add,
+    Mul,
+    Sub,
+};
+
+/// Represents a Montgomery curve Bv² = u³ + Au² + u over the ring Fq[ε]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MontgomeryCurve<const Q: u64> {
+    a: RingElement<Q>,
+    b: RingElement<Q>,
+}
+
+impl<const Q: u64> MontgomeryCurve<Q> {
+    /// Create a new Montgomery curve with coefficients A and B
+    pub fn new(a: RingElement<Q>, b: RingElement<Q>) -> Self {
+        let valid = b.mul(a.mul(a).sub(constant(4))).is_invertible();
+        assert!(valid, "B(A² - 4) must be invertible for a valid curve");
+
+        MontgomeryCurve { a, b }
+    }
+
+    /// Get the A coefficient of the curve
+    pub fn a(&self) -> RingElement<Q> {
+        self.a
+    }
+
+    /// Get the B coefficient of the curve
+    pub fn b(&self) -> RingElement<Q> {
+        self.b
+    }
+
+    /// Check if a point lies on BV²W = U³ + AU²W + UW²
+    pub fn contains(&self, point: &MontgomeryPoint<Q>) -> bool {
+        let (u, v, w) = (point.u, point.v, point.w);
+        let has_unit_coordinate =
+            u.is_invertible() || v.is_invertible() || w.is_invertible();
+
+        let lhs = self.b.mul(v).mul(v).mul(w);
+        let rhs = u
+            .mul(u)
+            .mul(u)
+            .add(self.a.mul(u).mul(u).mul(w))
+            .add(u.mul(w).mul(w));
+
+        has_unit_coordinate && lhs == rhs
+    }
+}
+
+/// Represents a point [U:V:W] on a Montgomery curve in homogeneous projective
+/// coordinates
+#[derive(Debug, Clone, Copy)]
+pub struct MontgomeryPoint<const Q: u64> {
+    u: RingElement<Q>,
+    v: RingElement<Q>,
+    w: RingElement<Q>,
+}
+
+impl<const Q: u64> MontgomeryPoint<Q> {
+    /// Create a new projective point [U:V:W]
+    pub const fn new(u: RingElement<Q>, v: RingElement<Q>, w: RingElement<Q>) -> Self {
+        MontgomeryPoint { u, v, w }
+    }
+
+    /// Create the identity element, the point at infinity [0:1:0]
+    pub fn identity() -> Self {
+        MontgomeryPoint::new(constant(0), constant(1), constant(0))
+    }
+
+    /// Get the u-coordinate
+    pub fn u(&self) -> RingElement<Q> {
+        self.u
+    }
+
+    /// Get the v-coordinate
+    pub fn v(&self) -> RingElement<Q> {
+        self.v
+    }
+
+    /// Get the w-coordinate
+    pub fn w(&self) -> RingElement<Q> {
+        self.w
+    }
+
+    /// Get the affine coordinates (U/W, V/W), or `None` if W is not invertible
+    pub fn to_affine(&self) -> Option<(RingElement<Q>, RingElement<Q>)> {
+        if !self.w.is_invertible() {
+            return None;
+        }
+
+        let w_inv = self.w.inv();
+        Some((self.u.mul(w_inv), self.v.mul(w_inv)))
+    }
+}
+
+/// Represents a twisted Edwards curve ax² + y² = 1 + dx²y² over the ring Fq[ε]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TwistedEdwardsCurve<const Q: u64> {
+    a: RingElement<Q>,
+    d: RingElement<Q>,
+}
+
+impl<const Q: u64> TwistedEdwardsCurve<Q> {
+    /// Create a new twisted Edwards curve with parameters a and d
+    pub fn new(a: RingElement<Q>, d: RingElement<Q>) -> Self {
+        let valid = a.mul(d).mul(a.sub(d)).is_invertible();
+        assert!(valid, "ad(a - d) must be invertible for a valid curve");
+
+        TwistedEdwardsCurve { a, d }
+    }
+
+    /// Get the a parameter of the curve
+    pub fn a(&self) -> RingElement<Q> {
+        self.a
+    }
+
+    /// Get the d parameter of the curve
+    pub fn d(&self) -> RingElement<Q> {
+        self.d
+    }
+
+    /// Check if a point lies on this curve
+    pub fn contains(&self, point: &EdwardsPoint<Q>) -> bool {
+        let (x_squared, y_squared) = (point.x.mul(point.x), point.y.mul(point.y));
+
+        self.a.mul(x_squared).add(y_squared)
+            == constant(1).add(self.d.mul(x_squared).mul(y_squared))
+    }
+
+    /// Add two points with the unified addition law, or `None` when one of its
+    /// denominators 1 ± dx₁x₂y₁y₂ is not invertible
+    ///
+    /// The law is complete when a is a square and d is not.
+    pub fn add(
+        &self,
+        p: &EdwardsPoint<Q>,
+        q: &EdwardsPoint<Q>,
+    ) -> Option<EdwardsPoint<Q>> {
+        assert!(self.contains(p), "Point P must be on the curve");
+        assert!(self.contains(q), "Point Q must be on the curve");
+
+        let one = constant(1);
+        let t = self.d.mul(p.x).mul(q.x).mul(p.y).mul(q.y);
+        let (x_denominator, y_denominator) = (one.add(t), one.sub(t));
+        if !(x_denominator.is_invertible() && y_denominator.is_invertible()) {
+            return None;
+        }
+
+        // x₃ = (x₁y₂ + y₁x₂)/(1 + t) and y₃ = (y₁y₂ - ax₁x₂)/(1 - t)
+        Some(EdwardsPoint::new(
+            p.x.mul(q.y).add(p.y.mul(q.x)).mul(x_denominator.inv()),
+            p.y.mul(q.y)
+                .sub(self.a.mul(p.x).mul(q.x))
+                .mul(y_denominator.inv()),
+        ))
+    }
+}
+
+/// Represents an affine point (x, y) on a twisted Edwards curve
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EdwardsPoint<const Q: u64> {
+    x: RingElement<Q>,
+    y: RingElement<Q>,
+}
+
+impl<const Q: u64> EdwardsPoint<Q> {
+    /// Create a new point (x, y)
+    pub const fn new(x: RingElement<Q>, y: RingElement<Q>) -> Self {
+        EdwardsPoint { x, y }
+    }
+
+    /// Create the identity element (0, 1)
+    pub fn identity() -> Self {
+        EdwardsPoint::new(constant(0), constant(1))
+    }
+
+    /// Get the x-coordinate
+    pub fn x(&self) -> RingElement<Q> {
+        self.x
+    }
+
+    /// Get the y-coordinate
+    pub fn y(&self) -> RingElement<Q> {
+        self.y
+    }
+}
+
+/// Maps between a twisted Hessian curve and its Montgomery and twisted Edwards models
+#[derive(Debug, Clone, Copy)]
+pub struct EdwardsMap<const Q: u64> {
+    curve: TwistedHessianCurve<Q>,
+    alpha: RingElement<Q>,
+    s: RingElement<Q>,
+    montgomery: MontgomeryCurve<Q>,
+    edwards: TwistedEdwardsCurve<Q>,
+}
+
+impl<const Q: u64> EdwardsMap<Q> {
+    /// Set up the maps for `curve`, or `None` unless q > 3 and its Weierstrass model
+    /// has a point (α, 0) of order 2 with 3α² + A a square
+    pub fn new(curve: TwistedHessianCurve<Q>) -> Option<Self> {
+        if Q <= 3 {
+            return None;
+        }
+
+        let weierstrass = curve.to_weierstrass();
+        let (big_a, big_b) = (weierstrass.a(), weierstrass.b());
+        let (a0, b0) = (big_a.constant(), big_b.constant());
+        let (zero, one) = (Fq::new(0), Fq::new(1));
+        let cubic = Polynomial::new(vec![b0, a0, zero, one]);
+
+        cubic.roots().into_iter().find_map(|alpha0| {
+            // the cubic at α₀ over Fq[ε] is a multiple of ε, and its derivative
+            // 3α₀² + A₀ is non-zero for a smooth curve, see
+            // [`TwistedHessianCurve::from_weierstrass`] for the same Hensel step
+            let derivative = cubic.derivative().evaluate(alpha0);
+            let x = RingElement::from_field(alpha0);
+            let value = x.mul(x).mul(x).add(big_a.mul(x)).add(big_b);
+            let alpha = RingElement::new(
+                alpha0,
+                zero.sub(value.epsilon_coeff().mul(derivative.inv())),
+            );
+
+            let s = sqrt(constant(3).mul(alpha).mul(alpha).add(big_a))?;
+            let s_inv = s.inv();
+            let montgomery =
+                MontgomeryCurve::new(constant(3).mul(alpha).mul(s_inv), s_inv);
+
+            // a_E = (A_M + 2)/B_M = 3α + 2s and d_E = (A_M - 2)/B_M = 3α - 2s
+            let three_alpha = constant(3).mul(alpha);
+            let two_s = constant(2).mul(s);
+            let edwards =
+                TwistedEdwardsCurve::new(three_alpha.add(two_s), three_alpha.sub(two_s));
+
+            Some(EdwardsMap {
+                curve,
+                alpha,
+                s,
+                montgomery,
+                edwards,
+            })
+        })
+    }
+
+    /// Get the twisted Hessian curve
+    pub fn curve(&self) -> TwistedHessianCurve<Q> {
+        self.curve
+    }
+
+    /// Get the Montgomery model
+    pub fn montgomery(&self) -> MontgomeryCurve<Q> {
+        self.montgomery
+    }
+
+    /// Get the twisted Edwards model
+    pub fn edwards(&self) -> TwistedEdwardsCurve<Q> {
+        self.edwards
+    }
+
+    /// Map a point of the curve to the Montgomery model
+    pub fn to_montgomery(&self, point: &Projective<Q>) -> MontgomeryPoint<Q> {
+        let wp = point.to_weierstrass(&self.curve);
+
+        MontgomeryPoint::new(
+            wp.x().sub(self.alpha.mul(wp.z())),
+            wp.y(),
+            self.s.mul(wp.z()),
+        )
+    }
+
+    /// Map a point of the Montgomery model back to the curve
+    pub fn from_montgomery(&self, point: &MontgomeryPoint<Q>) -> Projective<Q> {
+        assert!(
+            self.montgomery.contains(point),
+            "Point must be on the Montgomery curve"
+        );
+
+        // x = su + α and y = sv
+        let wp = WeierstrassPoint::new(
+            self.s.mul(point.u).add(self.alpha.mul(point.w)),
+            self.s.mul(point.v),
+            point.w,
+        );
+
+        Projective::from_weierstrass(&wp, &self.curve)
+    }
+
+    /// Map a point of the curve to the twisted Edwards model, or `None` at the
+    /// exceptional points
+    pub fn to_edwards(&self, point: &Projective<Q>) -> Option<EdwardsPoint<Q>> {
+        let MontgomeryPoint { u, v, w } = self.to_montgomery(point);
+        let zero = constant(0);
+
+        // the points reducing to infinity are [U:V:0] with V a unit
+        if w == zero {
+            return Some(EdwardsPoint::new(u.mul(v.inv()), constant(1)));
+        }
+        if u == zero && v == zero {
+            return Some(EdwardsPoint::new(zero, zero.sub(constant(1))));
+        }
+
+        let u_plus_w = u.add(w);
+        if !(v.is_invertible() && u_plus_w.is_invertible()) {
+            return None;
+        }
+
+        Some(EdwardsPoint::new(
+            u.mul(v.inv()),
+            u.sub(w).mul(u_plus_w.inv()),
+        ))
+    }
+
+    /// Map a point of the twisted Edwards model back to the curve, or `None` at the
+    /// exceptional points
+    pub fn from_edwards(&self, point: &EdwardsPoint<Q>) -> Option<Projective<Q>> {
+        assert!(
+            self.edwards.contains(point),
+            "Point must be on the twisted Edwards curve"
+        );
+
+        let (x, y) = (point.x, point.y);
+        let (zero, one) = (constant(0), constant(1));
+        if x == zero && y == zero.sub(one) {
+            let order_two = MontgomeryPoint::new(zero, zero, one);
+            return Some(self.from_montgomery(&order_two));
+        }
+
+        // u = (1 + y)/(1 - y) and v = u/x, scaled by (1 - y)x
+        let montgomery =
+            MontgomeryPoint::new(one.add(y).mul(x), one.add(y), one.sub(y).mul(x));
+        let has_unit_coordinate = montgomery.u.is_invertible()
+            || montgomery.v.is_invertible()
+            || montgomery.w.is_invertible();
+
+        has_unit_coordinate.then(|| self.from_montgomery(&montgomery))
+    }
+}
+
+fn constant<const Q: u64>(value: u64) -> RingElement<Q> {
+    RingElement::from_field(Fq::new(value))
+}
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{
+        all_points,
+        paper_curve,
+    };
+
+    fn curve_over_f7() -> TwistedHessianCurve<7> {
+        type F7 = Fq<7>;
+
+        // the residue curve has 12 points and a single one of order 2
+        let a = RingElement::new(F7::new(3), F7::new(1)); // 3+ε
+        let d = RingElement::new(F7::new(1), F7::new(3)); // 1+3ε
+        TwistedHessianCurve::new(a, d)
+    }
+
+    #[test]
+    fn to_montgomery__maps_all_points_and_back() {
+        let curve = curve_over_f7();
+        let map = EdwardsMap::new(curve).unwrap();
+        let points = all_points(&curve);
+
+        // q·#E(F7) = 7·12
+        assert_eq!(points.len(), 84);
+        for point in points {
+            let mapped = map.to_montgomery(&point);
+
+            assert!(map.montgomery().contains(&mapped));
+            assert_eq!(map.from_montgomery(&mapped), point);
+        }
+    }
+
+    #[test]
+    fn to_edwards__is_a_homomorphism_over_all_points() {
+        let curve = curve_over_f7();
+        let map = EdwardsMap::new(curve).unwrap();
+        let edwards = map.edwards();
+        let points = all_points(&curve);
+
+        assert_eq!(
+            map.to_edwards(&Projective::identity()),
+            Some(EdwardsPoint::identity())
+        );
+
+        let mut exceptional = 0u32;
+        for p in &points {
+            let Some(ep) = map.to_edwards(p) else {
+                let mp = map.to_montgomery(p);
+                let (v0, u0_plus_w0) = (mp.v().constant(), mp.u().add(mp.w()).constant());
+                assert!(v0.value() == 0 || u0_plus_w0.value() == 0);
+                exceptional = exceptional.saturating_add(1);
+                continue;
+            };
+            assert!(edwards.contains(&ep));
+            assert_eq!(map.from_edwards(&ep), Some(*p));
+
+            for q in &points {
+                let (Some(eq), Some(sum)) =
+                    (map.to_edwards(q), map.to_edwards(&curve.add(p, q)))
+                else {
+                    continue;
+                };
+                if let Some(edwards_sum) = edwards.add(&ep, &eq) {
+                    assert_eq!(edwards_sum, sum);
+                }
+            }
+        }
+
+        // the lifts of (0, 0) and of the two points with u = -1, less (0, 0) itself
+        assert_eq!(exceptional, 20);
+    }
+
+    #[test]
+    fn new__should_reject_curves_without_points_of_order_2() {
+        // the paper 3.1 residue curve has 9 points
+        assert!(EdwardsMap::new(paper_curve()).is_none());
+    }
+
+    #[test]
+    fn new__should_reject_curves_whose_points_of_order_2_give_no_square() {
+        type F7 = Fq<7>;
+
+        // the residue curve 2X³ + Y³ + Z³ = 0 has 3 points of order 2 but none of
+        // order 4, and 3α² + A is a non-square at each of them
+        let curve = TwistedHessianCurve::new(
+            RingElement::new(F7::new(2), F7::new(1)),
+            RingElement::new(F7::new(0), F7::new(3)),
+        );
+        assert!(EdwardsMap::new(curve).is_none());
+    }
+}
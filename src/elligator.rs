@@ -0,0 +1,314 @@
+//! Elligator 2 encoding of points as uniformly random strings
+//!
+//! The residue curve is mapped to its short Weierstrass model y² = x³ + ax + b, which
+//! for a root ρ of x³ + ax + b, i.e. a point (ρ, 0) of order 2, is shifted to
+//! y² = x'³ + Ax'² + Bx' with x' = x - ρ, A = 3ρ and B = 3ρ² + a. Elligator 2
+//! (Bernstein, Hamburg, Krasnova & Lange, 2013, §5) then maps r ∈ Fq with a fixed
+//! non-square u through v = -A/(1 + ur²) to x' = v if v³ + Av² + Bv is a square and
+//! x' = -v - A otherwise, y being the even square root in the second case and the odd
+//! one in the first. Since r and -r give the same point, the map is injective on
+//! representatives of either sign and inverted by solving for r².
+//!
+//! A point over Fq[ε] is represented by r₀ + r₁ε, where r₀ encodes its reduction and
+//! r₁ is the parameter of [`TwistedHessianCurve::lift_point`] giving the point from
+//! it. About half of the points have a representative.
+
+use crate::{
+    curve::TwistedHessianCurve,
+    extension::Fq2,
+    field::Fq,
+    poly::Polynomial,
+    projective::Projective,
+    ring::RingElement,
+    weierstrass::WeierstrassPoint,
+};
+use alloc::vec;
+use core::ops::{
+    Add,
+    Mul,
+    Sub,
+};
+#[cfg(feature = "rand")]
+use rand_core::RngCore;
+
+/// Size in bytes of the string encoding a representative, see [`Elligator::encode`]
+pub const REPRESENTATIVE_SIZE: usize = 16;
+
+/// Elligator 2 map between Fq[ε] and the points of a twisted Hessian curve
+#[derive(Debug, Clone, Copy)]
+pub struct Elligator<const Q: u64> {
+    curve: TwistedHessianCurve<Q>,
+    rho: Fq<Q>,
+    big_a: Fq<Q>,
+    big_b: Fq<Q>,
+    non_square: Fq<Q>,
+}
+
+impl<const Q: u64> Elligator<Q> {
+    /// Set up the map for `curve`, or `None` unless q > 3 and the residue curve has a
+    /// point of order 2 whose Weierstrass x-coordinate ρ is non-zero
+    pub fn new(curve: TwistedHessianCurve<Q>) -> Option<Self> {
+        if Q <= 3 {
+            return None;
+        }
+
+        let weierstrass = curve.reduce().to_weierstrass();
+        let (a, b) = (weierstrass.a().constant(), weierstrass.b().constant());
+        let (zero, one, three, four) = (Fq::new(0), Fq::new(1), Fq::new(3), Fq::new(4));
+        let rho = *Polynomial::new(vec![b, a, zero, one])
+            .roots()
+            .iter()
+            .find(|rho| rho.value() != 0)?;
+
+        // A = 3ρ and B = 3ρ² + a, with AB(A² - 4B) ≠ 0 for a smooth curve
+        let big_a = three.mul(rho);
+        let big_b = three.mul(rho).mul(rho).add(a);
+        let discriminant = big_a.mul(big_a).sub(four.mul(big_b));
+        if big_a.mul(big_b).mul(discriminant).value() == 0 {
+            return None;
+        }
+
+        Some(Elligator {
+            curve,
+            rho,
+            big_a,
+            big_b,
+            non_square: Fq2::<Q>::NON_RESIDUE,
+        })
+    }
+
+    /// Get the curve
+    pub fn curve(&self) -> TwistedHessianCurve<Q> {
+        self.curve
+    }
+
+    /// Map a representative r₀ + r₁ε to its point, or `None` for the few r₀ with
+    /// 1 + ur₀² = 0 or v³ + Av² + Bv = 0
+    pub fn map(&self, r: RingElement<Q>) -> Option<Projective<Q>> {
+        let residue = self.map_residue(r.constant())?;
+
+        Some(self.curve.lift_point(&residue, r.epsilon_coeff()))
+    }
+
+    /// Find the representative of a point whose constant part is even, or `None` when
+    /// the point is not in the image of the map
+    pub fn representative(&self, point: &Projective<Q>) -> Option<RingElement<Q>> {
+        assert!(self.curve.contains(point), "Point must be on the curve");
+
+        let residue_curve = self.curve.reduce();
+        let (x, y) = point.reduce().to_weierstrass(&residue_curve).to_affine()?;
+        let (x, y) = (x.constant().sub(self.rho), y.constant());
+        let (zero, a, u) = (Fq::new(0), self.big_a, self.non_square);
+
+        // r² = -x'/(u(x' + A)) for an even y and r² = -(x' + A)/(ux') for an odd one
+        let (numerator, denominator) = if sgn0(y) == 0 {
+            (x, u.mul(x.add(a)))
+        } else {
+            (x.add(a), u.mul(x))
+        };
+        if denominator.value() == 0 {
+            return None;
+        }
+        let r0 = zero.sub(numerator.mul(denominator.inv())).sqrt()?;
+        let r0 = Fq::conditional_select(&r0, &zero.sub(r0), sgn0(r0) == 1);
+
+        // points of order 2 and the excluded inputs need the check
+        let residue = self.map_residue(r0)?;
+        if residue != point.reduce() {
+            return None;
+        }
+        let r1 = self.curve.lift_parameter(&residue, point)?;
+
+        Some(RingElement::new(r0, r1))
+    }
+
+    /// Encode a point as a uniformly random string, or `None` when it has no
+    /// representative
+    ///
+    /// Each half of the string holds a big-endian u64 congruent to r₀ or r₁ modulo q,
+    /// the sign of r₀ and the multiple of q being random, so that strings of encodable
+    /// points uniform on the curve are uniform up to a bias of q/2⁶⁴.
+    #[cfg(feature = "rand")]
+    pub fn encode(
+        &self,
+        point: &Projective<Q>,
+        rng: &mut impl RngCore,
+    ) -> Option<[u8; REPRESENTATIVE_SIZE]> {
+        let r = self.representative(point)?;
+        let negate = rng.next_u32() & 1 == 1;
+        let r0 =
+            Fq::conditional_select(&r.constant(), &Fq::new(0).sub(r.constant()), negate);
+
+        let mut bytes = [0u8; REPRESENTATIVE_SIZE];
+        for (chunk, value) in bytes.chunks_exact_mut(8).zip([r0, r.epsilon_coeff()]) {
+            chunk.copy_from_slice(&pad(value, rng).to_be_bytes());
+        }
+
+        Some(bytes)
+    }
+
+    /// Decode a string of [`Elligator::encode`], or any other string of the same size,
+    /// to a point
+    pub fn decode(&self, bytes: &[u8; REPRESENTATIVE_SIZE]) -> Option<Projective<Q>> {
+        let [r0, r1] = [&bytes[..8], &bytes[8..]].map(|half| {
+            let value = u64::from_be_bytes(half.try_into().expect("half of 16 bytes"));
+            Fq::new(value.checked_rem(Q).expect("q is non-zero"))
+        });
+
+        self.map(RingElement::new(r0, r1))
+    }
+
+    /// Map r ∈ Fq to the residue curve through its Weierstrass model
+    fn map_residue(&self, r: Fq<Q>) -> Option<Projective<Q>> {
+        let (zero, one, a, b) = (Fq::new(0), Fq::new(1), self.big_a, self.big_b);
+        let g = |x: Fq<Q>| x.mul(x).mul(x).add(a.mul(x).mul(x)).add(b.mul(x));
+
+        let denominator = one.add(self.non_square.mul(r).mul(r));
+        if denominator.value() == 0 {
+            return None;
+        }
+        let v = zero.sub(a.mul(denominator.inv()));
+        let gv = g(v);
+        if gv.value() == 0 {
+            return None;
+        }
+
+        let (x, odd) = if gv.is_square() {
+            (v, true)
+        } else {
+            (zero.sub(v).sub(a), false)
+        };
+        let y = g(x).sqrt().expect("g(-v - A) = ur²g(v) is a square");
+        let y = Fq::conditional_select(&y, &zero.sub(y), (sgn0(y) == 1) != odd);
+
+        let residue_curve = self.curve.reduce();
+        let point = WeierstrassPoint::from_affine(
+            RingElement::from_field(x.add(self.rho)),
+            RingElement::from_field(y),
+        );
+
+        Some(Projective::from_weierstrass(&point, &residue_curve))
+    }
+}
+
+/// The parity of an element
+fn sgn0<const Q: u64>(x: Fq<Q>) -> u64 {
+    x.value() & 1
+}
+
+/// A uniform u64 below the largest multiple of q that is congruent to `value`
+#[cfg(feature = "rand")]
+fn pad<const Q: u64>(value: Fq<Q>, rng: &mut impl RngCore) -> u64 {
+    let multiples = u64::MAX.checked_div(Q).expect("q is non-zero");
+
+    loop {
+        let k =
+            rng.next_u64() & u64::MAX.checked_shr(multiples.leading_zeros()).unwrap_or(0);
+        if k < multiples {
+            return k.wrapping_mul(Q).wrapping_add(value.value());
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "rand")]
+    use rand::{
+        SeedableRng,
+        rngs::StdRng,
+    };
+
+    fn curve_over_f7() -> TwistedHessianCurve<7> {
+        type F7 = Fq<7>;
+
+        // the residue curve 2X³ + Y³ + Z³ = 0 has 3 points of order 2
+        let a = RingElement::new(F7::new(2), F7::new(1)); // 2+ε
+        let d = RingElement::new(F7::new(0), F7::new(3)); // 3ε
+        TwistedHessianCurve::new(a, d)
+    }
+
+    #[test]
+    fn representative__inverts_map() {
+        let curve = curve_over_f7();
+        let elligator = Elligator::new(curve).unwrap();
+
+        for r0 in (0..7).map(Fq::new) {
+            for r1 in (0..7).map(Fq::new) {
+                let r = RingElement::new(r0, r1);
+                let Some(point) = elligator.map(r) else {
+                    continue;
+                };
+                assert!(curve.contains(&point));
+
+                let canonical = elligator.representative(&point).unwrap();
+                assert_eq!(canonical.epsilon_coeff(), r1);
+                assert!(
+                    canonical.constant() == r0
+                        || canonical.constant() == Fq::new(0).sub(r0)
+                );
+                assert_eq!(elligator.map(canonical), Some(point));
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn representative__exists_for_about_half_of_the_points() {
+        const Q: u64 = 1009;
+
+        let curve = TwistedHessianCurve::<Q>::new(
+            RingElement::new(Fq::new(1), Fq::new(7)),
+            RingElement::new(Fq::new(2), Fq::new(3)),
+        );
+        let elligator = Elligator::new(curve).unwrap();
+        let mut rng = StdRng::seed_from_u64(49);
+
+        let mut encodable = 0u32;
+        for _ in 0..1000 {
+            let point = curve.random_point(&mut rng);
+            if let Some(r) = elligator.representative(&point) {
+                assert_eq!(elligator.map(r), Some(point));
+                encodable = encodable.saturating_add(1);
+            }
+        }
+
+        assert!((400..=600).contains(&encodable));
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn decode__inverts_encode() {
+        const Q: u64 = 2_147_483_647;
+
+        let curve = TwistedHessianCurve::<Q>::new(
+            RingElement::new(Fq::new(2), Fq::new(7)),
+            RingElement::new(Fq::new(5), Fq::new(3)),
+        );
+        let elligator = Elligator::new(curve).unwrap();
+        let mut rng = StdRng::seed_from_u64(16);
+
+        let mut encoded = 0u32;
+        for _ in 0..50 {
+            let point = curve.random_point(&mut rng);
+            if let Some(bytes) = elligator.encode(&point, &mut rng) {
+                assert_eq!(elligator.decode(&bytes), Some(point));
+                encoded = encoded.saturating_add(1);
+            }
+        }
+        assert!(encoded > 10);
+    }
+
+    #[test]
+    fn new__should_reject_curves_without_points_of_order_2() {
+        // the paper 3.1 residue curve has 9 points
+        type F5 = Fq<5>;
+
+        let curve = TwistedHessianCurve::new(
+            RingElement::from_field(F5::new(1)),
+            RingElement::from_field(F5::new(1)),
+        );
+        assert!(Elligator::new(curve).is_none());
+    }
+}
@@ -0,0 +1,279 @@
+//! Byte encoding of points on a twisted Hessian curve
+//!
+//! A point is written as a tag byte followed by big-endian field elements of
+//! ⌈log₂ q / 8⌉ bytes each, a ring element c₀ + c₁ε being c₀ followed by c₁:
+//!
+//! - `0x00`: the identity, no payload
+//! - `0x02 + i` (i < 3): the finite point [x:y:1] as x alone, where y mod ε is the
+//!   i-th smallest root of y³ - d₀x₀y + a₀x₀³ + 1 and its ε-part follows by Hensel
+//!   lifting
+//! - `0x05`: the finite point [x:y:1] as x and y, used when y mod ε is a repeated root
+//!   and cannot be lifted uniquely
+//! - `0x06`: the point [x:1:z] with z ≡ 0 mod ε, as x and z
+
+use crate::{
+    affine::Affine,
+    curve::TwistedHessianCurve,
+    field::Fq,
+    poly::Polynomial,
+    projective::Projective,
+    ring::RingElement,
+};
+use alloc::{
+    vec,
+    vec::Vec,
+};
+use core::ops::{
+    Add,
+    Mul,
+    Sub,
+};
+
+/// Tag of the identity element
+pub const TAG_IDENTITY: u8 = 0x00;
+
+/// Tag of the compressed finite point whose y mod ε is the smallest root; the next two
+/// tags select the second and third root
+pub const TAG_COMPRESSED: u8 = 0x02;
+
+/// Tag of an uncompressed finite point
+pub const TAG_UNCOMPRESSED: u8 = 0x05;
+
+/// Tag of a point with Z ≡ 0 mod ε
+pub const TAG_AT_INFINITY: u8 = 0x06;
+
+impl<const Q: u64> Projective<Q> {
+    /// Encode a point on `curve`, compressing it whenever y can be recovered from x
+    pub fn to_bytes(&self, curve: &TwistedHessianCurve<Q>) -> Vec<u8> {
+        assert!(curve.contains(self), "Point must be on the curve");
+
+        match self.to_affine() {
+            Affine::Identity => vec![TAG_IDENTITY],
+            Affine::Finite { x, y } => {
+                let roots = residue_cubic(curve, x.constant()).roots();
+                let index = roots
+                    .iter()
+                    .position(|r| *r == y.constant())
+                    .expect("y mod ε is a root for points on the curve");
+
+                if lift_y(curve, x, y.constant()) == Some(y) {
+                    let offset =
+                        u8::try_from(index).expect("a cubic has at most 3 roots");
+                    let mut bytes = vec![TAG_COMPRESSED.saturating_add(offset)];
+                    write_ring(&mut bytes, x);
+                    bytes
+                } else {
+                    let mut bytes = vec![TAG_UNCOMPRESSED];
+                    write_ring(&mut bytes, x);
+                    write_ring(&mut bytes, y);
+                    bytes
+                }
+            }
+            Affine::AtInfinity { x, z } => {
+                let mut bytes = vec![TAG_AT_INFINITY];
+                write_ring(&mut bytes, x);
+                write_ring(&mut bytes, z);
+                bytes
+            }
+        }
+    }
+
+    /// Decode a point on `curve`
+    ///
+    /// Returns `None` unless the bytes are exactly the canonical encoding of a point on
+    /// the curve, so every point has a single accepted encoding.
+    pub fn from_bytes(bytes: &[u8], curve: &TwistedHessianCurve<Q>) -> Option<Self> {
+        let (&tag, payload) = bytes.split_first()?;
+        // a ring element is two field elements
+        let ring_len = field_len::<Q>().saturating_mul(2);
+        let pair_len = ring_len.saturating_mul(2);
+
+        let affine = match tag {
+            TAG_IDENTITY if payload.is_empty() => Affine::Identity,
+            TAG_COMPRESSED..TAG_UNCOMPRESSED if payload.len() == ring_len => {
+                let x = read_ring(payload)?;
+                let index = usize::from(tag.saturating_sub(TAG_COMPRESSED));
+                let y0 = *residue_cubic(curve, x.constant()).roots().get(index)?;
+                let y = lift_y(curve, x, y0)?;
+                Affine::Finite { x, y }
+            }
+            TAG_UNCOMPRESSED if payload.len() == pair_len => {
+                let (x, y) = payload.split_at(ring_len);
+                Affine::Finite {
+                    x: read_ring(x)?,
+                    y: read_ring(y)?,
+                }
+            }
+            TAG_AT_INFINITY if payload.len() == pair_len => {
+                let (x, z) = payload.split_at(ring_len);
+                Affine::AtInfinity {
+                    x: read_ring(x)?,
+                    z: read_ring(z)?,
+                }
+            }
+            _ => return None,
+        };
+
+        let point = affine.to_projective();
+        (curve.contains(&point) && point.to_bytes(curve) == bytes).then_some(point)
+    }
+}
+
+/// Number of bytes of an encoded field element
+fn field_len<const Q: u64>() -> usize {
+    let bits = u64::BITS.saturating_sub(Q.saturating_sub(1).leading_zeros());
+    bits.div_ceil(8).max(1) as usize
+}
+
+fn write_ring<const Q: u64>(bytes: &mut Vec<u8>, element: RingElement<Q>) {
+    let start = 8usize.saturating_sub(field_len::<Q>());
+    for coeff in [element.constant(), element.epsilon_coeff()] {
+        bytes.extend_from_slice(&coeff.value().to_be_bytes()[start..]);
+    }
+}
+
+/// Read a ring element from exactly two field elements, rejecting unreduced values
+fn read_ring<const Q: u64>(bytes: &[u8]) -> Option<RingElement<Q>> {
+    let (constant, epsilon) = bytes.split_at(field_len::<Q>());
+    Some(RingElement::new(
+        read_field(constant)?,
+        read_field(epsilon)?,
+    ))
+}
+
+fn read_field<const Q: u64>(bytes: &[u8]) -> Option<Fq<Q>> {
+    let mut buf = [0u8; 8];
+    buf[8usize.saturating_sub(bytes.len())..].copy_from_slice(bytes);
+    let value = u64::from_be_bytes(buf);
+    (value < Q).then(|| Fq::new(value))
+}
+
+/// The curve equation at Z = 1 as a cubic in y over the residue field,
+/// y³ - d₀x₀y + a₀x₀³ + 1
+fn residue_cubic<const Q: u64>(
+    curve: &TwistedHessianCurve<Q>,
+    x0: Fq<Q>,
+) -> Polynomial<Q> {
+    let a0 = curve.a().constant();
+    let d0 = curve.d().constant();
+    let zero = Fq::new(0);
+
+    Polynomial::new(vec![
+        a0.mul(x0).mul(x0).mul(x0).add(Fq::new(1)),
+        zero.sub(d0.mul(x0)),
+        zero,
+        Fq::new(1),
+    ])
+}
+
+/// Lift a simple root y₀ of the residue cubic to the unique y = y₀ + y₁ε on the curve
+/// above x, or `None` if y₀ is a repeated root
+fn lift_y<const Q: u64>(
+    curve: &TwistedHessianCurve<Q>,
+    x: RingElement<Q>,
+    y0: Fq<Q>,
+) -> Option<RingElement<Q>> {
+    let derivative = residue_cubic(curve, x.constant()).derivative().evaluate(y0);
+    if derivative.value() == 0 {
+        return None;
+    }
+
+    // F(x, y₀ + y₁ε) = F(x, y₀) + y₁·∂F/∂y(x₀, y₀)·ε, and F(x, y₀) is a multiple of ε
+    let y = RingElement::from_field(y0);
+    let one = RingElement::from_field(Fq::new(1));
+    let value = curve
+        .a()
+        .mul(x)
+        .mul(x)
+        .mul(x)
+        .add(y.mul(y).mul(y))
+        .add(one)
+        .sub(curve.d().mul(x).mul(y));
+    let y1 = Fq::new(0).sub(value.epsilon_coeff().mul(derivative.inv()));
+
+    Some(RingElement::new(y0, y1))
+}
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::paper_curve_and_generator;
+
+    #[test]
+    fn to_bytes__kats_paper_3_1() {
+        let (curve, generator) = paper_curve_and_generator();
+
+        // P = [1:2:3+ε] = [2+ε : 4+2ε : 1], and 4 is the larger of the residue roots 3, 4
+        assert_eq!(generator.to_bytes(&curve), [TAG_COMPRESSED + 1, 2, 1]);
+        assert_eq!(Projective::identity().to_bytes(&curve), [TAG_IDENTITY]);
+    }
+
+    #[test]
+    fn from_bytes__round_trips_over_all_points() {
+        let (curve, generator) = paper_curve_and_generator();
+
+        // P has order 45 and generates every point of the curve
+        for k in 0..45u64 {
+            let point = generator.scalar_mul(k, curve.a());
+            let bytes = point.to_bytes(&curve);
+
+            let decoded = Projective::from_bytes(&bytes, &curve).unwrap();
+            assert!(decoded.is_equal(&point));
+        }
+    }
+
+    #[test]
+    fn from_bytes__round_trips_over_paper_3_2_points() {
+        type F11 = Fq<11>;
+
+        let a = RingElement::new(F11::new(1), F11::new(2)); // 1+2ε
+        let d = RingElement::new(F11::new(2), F11::new(1)); // 2+ε
+        let curve = TwistedHessianCurve::new(a, d);
+        let p = Projective::new(
+            RingElement::from_field(F11::new(1)),
+            RingElement::new(F11::new(7), F11::new(6)),
+            RingElement::new(F11::new(4), F11::new(6)),
+        );
+
+        for k in 0..200u64 {
+            let point = p.scalar_mul(k, a);
+            let bytes = point.to_bytes(&curve);
+
+            assert!(
+                Projective::from_bytes(&bytes, &curve)
+                    .unwrap()
+                    .is_equal(&point)
+            );
+        }
+    }
+
+    #[test]
+    fn from_bytes__should_reject_invalid_encodings() {
+        let (curve, generator) = paper_curve_and_generator();
+        let reject =
+            |bytes: &[u8]| assert!(Projective::from_bytes(bytes, &curve).is_none());
+
+        // malformed
+        reject(&[]);
+        reject(&[0x07, 2, 1]);
+        reject(&[TAG_IDENTITY, 0]);
+        reject(&[TAG_COMPRESSED + 1, 2]);
+        // unreduced coordinate
+        reject(&[TAG_COMPRESSED + 1, 7, 1]);
+        // x₀ = 2 has only two distinct roots
+        reject(&[TAG_COMPRESSED + 2, 2, 1]);
+        // not on the curve
+        reject(&[TAG_UNCOMPRESSED, 2, 1, 4, 3]);
+        // uncompressed encoding of a compressible point
+        reject(&[TAG_UNCOMPRESSED, 2, 1, 4, 2]);
+        // the identity [0:-1:1] spelled as a finite point
+        reject(&[TAG_UNCOMPRESSED, 0, 0, 4, 0]);
+
+        assert!(
+            Projective::from_bytes(&[TAG_COMPRESSED + 1, 2, 1], &curve)
+                .unwrap()
+                .is_equal(&generator)
+        );
+    }
+}
@@ -0,0 +1,192 @@
+//! Quadratic extension Fq² = Fq[i]/(i² - ν) of a prime field, ν a non-residue
+
+use crate::field::Fq;
+use core::ops::{
+    Add,
+    Mul,
+    Sub,
+};
+
+/// Element c₀ + c₁i of the quadratic extension Fq², with i² = ν the smallest
+/// quadratic non-residue of Fq
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fq2<const Q: u64> {
+    c0: Fq<Q>,
+    c1: Fq<Q>,
+}
+
+impl<const Q: u64> Fq2<Q> {
+    /// The non-residue ν = i²
+    pub const NON_RESIDUE: Fq<Q> = Fq::new(smallest_non_residue(Q));
+
+    /// Create a new element c₀ + c₁i
+    pub const fn new(c0: Fq<Q>, c1: Fq<Q>) -> Self {
+        Fq2 { c0, c1 }
+    }
+
+    /// Embed an element of Fq
+    pub const fn from_base(c0: Fq<Q>) -> Self {
+        Fq2::new(c0, Fq::new(0))
+    }
+
+    /// Get the Fq part c₀
+    pub fn c0(&self) -> Fq<Q> {
+        self.c0
+    }
+
+    /// Get the coefficient c₁ of i
+    pub fn c1(&self) -> Fq<Q> {
+        self.c1
+    }
+
+    /// Check if this is zero
+    pub fn is_zero(&self) -> bool {
+        self.c0.value() == 0 && self.c1.value() == 0
+    }
+
+    /// Check if this lies in Fq, i.e. c₁ = 0
+    pub fn is_in_base_field(&self) -> bool {
+        self.c1.value() == 0
+    }
+
+    /// Frobenius x ↦ x^q, which is the conjugation c₀ + c₁i ↦ c₀ - c₁i
+    pub fn frobenius(&self) -> Self {
+        Fq2::new(self.c0, Fq::new(0).sub(self.c1))
+    }
+
+    /// Multiplicative inverse, through the norm c₀² - νc₁² in Fq
+    pub fn inv(&self) -> Self {
+        assert!(!self.is_zero(), "Cannot invert zero");
+
+        let norm = self
+            .c0
+            .mul(self.c0)
+            .sub(Self::NON_RESIDUE.mul(self.c1).mul(self.c1));
+        let norm_inv = norm.inv();
+
+        Fq2::new(self.c0.mul(norm_inv), Fq::new(0).sub(self.c1.mul(norm_inv)))
+    }
+
+    /// Raise to a power by square-and-multiply
+    pub fn pow(&self, exponent: u64) -> Self {
+        let mut result = Fq2::from_base(Fq::new(1));
+        let mut base = *self;
+        let mut exp = exponent;
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.mul(base);
+            }
+            base = base.mul(base);
+            exp >>= 1;
+        }
+
+        result
+    }
+}
+
+/// Smallest n with n^((q-1)/2) ≡ -1 mod q
+const fn smallest_non_residue(q: u64) -> u64 {
+    assert!(q % 2 == 1, "Fq² needs an odd q");
+
+    let modulus = q as u128;
+    let exponent = q.wrapping_sub(1) / 2;
+    let mut n = 2u64;
+
+    loop {
+        // n^((q-1)/2) mod q by square-and-multiply in u128
+        let mut result = 1u128;
+        let mut base = n as u128;
+        let mut exp = exponent;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result
+                    .wrapping_mul(base)
+                    .checked_rem(modulus)
+                    .expect("q is non-zero");
+            }
+            base = base
+                .wrapping_mul(base)
+                .checked_rem(modulus)
+                .expect("q is non-zero");
+            exp >>= 1;
+        }
+
+        if result == modulus.wrapping_sub(1) {
+            return n;
+        }
+        n = n.wrapping_add(1);
+    }
+}
+
+#[allow(clippy::arithmetic_side_effects)]
+impl<const Q: u64> Add for Fq2<Q> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Fq2::new(self.c0 + rhs.c0, self.c1 + rhs.c1)
+    }
+}
+
+#[allow(clippy::arithmetic_side_effects)]
+impl<const Q: u64> Sub for Fq2<Q> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Fq2::new(self.c0 - rhs.c0, self.c1 - rhs.c1)
+    }
+}
+
+#[allow(clippy::arithmetic_side_effects)]
+impl<const Q: u64> Mul for Fq2<Q> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        // (a + bi)(c + di) = ac + νbd + (ad + bc)i
+        Fq2::new(
+            self.c0 * rhs.c0 + Self::NON_RESIDUE * self.c1 * rhs.c1,
+            self.c0 * rhs.c1 + self.c1 * rhs.c0,
+        )
+    }
+}
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_residue__is_not_a_square() {
+        assert_eq!(Fq2::<7>::NON_RESIDUE.value(), 3);
+        assert_eq!(Fq2::<11>::NON_RESIDUE.value(), 2);
+        assert_eq!(Fq2::<41>::NON_RESIDUE.value(), 3);
+
+        for x in (0..41).map(Fq::<41>::new) {
+            assert_ne!(x.mul(x), Fq2::<41>::NON_RESIDUE);
+        }
+    }
+
+    #[test]
+    fn inv__proptest() {
+        type F = Fq2<41>;
+
+        for c0 in 0..41 {
+            for c1 in 0..41 {
+                let x = F::new(Fq::new(c0), Fq::new(c1));
+                if !x.is_zero() {
+                    assert_eq!(x.mul(x.inv()), F::from_base(Fq::new(1)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn frobenius__is_the_qth_power() {
+        type F = Fq2<13>;
+
+        let x = F::new(Fq::new(3), Fq::new(7));
+        assert_eq!(x.frobenius(), x.pow(13));
+        assert_eq!(x.pow(13 * 13), x);
+        assert!(x.mul(x.frobenius()).is_in_base_field());
+    }
+}
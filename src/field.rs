@@ -7,7 +7,7 @@ use core::ops::{
 };
 
 /// Finite field Fq implementation where q is a prime power
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Fq<const Q: u64> {
     value: u64,
 }
@@ -101,13 +101,22 @@ impl<const Q: u64> Fq<Q> {
         result
     }
 
+    /// Select `a` if `choice` is false and `b` if it is true, without branching
+    pub fn conditional_select(a: &Self, b: &Self, choice: bool) -> Self {
+        // all ones when choice is set, all zeros otherwise
+        let mask = 0u64.wrapping_sub(u64::from(choice));
+        Fq {
+            value: (a.value & !mask) | (b.value & mask),
+        }
+    }
+
     /// This is needed for twisted Hessian curve conditions
     pub fn is_minus_three_square() -> bool {
         // -3 mod p = p-3 mod p
         let minus_three = Fq::<Q>::new(Q.checked_sub(3).expect("subtraction failed"));
 
         // a^((p-1)/2) ≡ 1 mod p, if a is a quadratic residue
-        if Q % 2 == 0 {
+        if Q.is_multiple_of(2) {
             return false;
         }
 
@@ -116,6 +125,140 @@ impl<const Q: u64> Fq<Q> {
             .expect("division failed");
         minus_three.pow(exponent).value == 1
     }
+
+    /// Primitive cube root of unity ω, which exists iff q ≡ 1 mod 3
+    pub fn cube_root_of_unity() -> Option<Self> {
+        if Q.checked_rem(3) != Some(1) {
+            return None;
+        }
+
+        // z^((q-1)/3) is a cube root of unity, primitive unless z is a cube
+        let exponent = Q.checked_sub(1).expect("subtraction failed") / 3;
+        (2..Q)
+            .map(|z| Fq::<Q>::new(z).pow(exponent))
+            .find(|omega| omega.value != 1)
+    }
+
+    /// A cube root of the element, if it has one
+    ///
+    /// Cubing is a bijection unless q ≡ 1 mod 3, in which case the other roots are
+    /// this one times ω and ω².
+    pub fn cube_root(&self) -> Option<Self> {
+        let q_minus_one = Q.checked_sub(1).expect("subtraction failed");
+        if self.value == 0 || Q == 3 {
+            return Some(*self);
+        }
+        if Q.checked_rem(3) != Some(1) {
+            // 3 · (2q - 1)/3 ≡ 1 mod q - 1
+            let exponent = Q
+                .checked_mul(2)
+                .and_then(|q| q.checked_sub(1))
+                .expect("Q is less than i64::MAX")
+                / 3;
+            return Some(self.pow(exponent));
+        }
+        if self.pow(q_minus_one / 3).value != 1 {
+            return None;
+        }
+
+        // Tonelli-Shanks for cube roots: q - 1 = 3^s·t with 3 ∤ t
+        let mut s = 0u32;
+        let mut t = q_minus_one;
+        while t.checked_rem(3) == Some(0) {
+            s = s.saturating_add(1);
+            t /= 3;
+        }
+
+        // x₀ = a^u with 3u ≡ 1 mod t, so b = x₀³/a lies in the 3-Sylow subgroup
+        let u = if t.checked_rem(3) == Some(1) {
+            t.checked_mul(2).and_then(|t| t.checked_add(1))
+        } else {
+            t.checked_add(1)
+        }
+        .expect("t is less than Q")
+            / 3;
+        let x0 = self.pow(u);
+        let b = x0.pow(3).mul(self.inv());
+
+        // c = z^t for a non-cube z generates the 3-Sylow subgroup, find b = c^e digit
+        // by digit
+        let c = (2..Q)
+            .map(Fq::<Q>::new)
+            .find(|z| z.pow(q_minus_one / 3).value != 1)
+            .expect("non-cubes exist when q ≡ 1 mod 3")
+            .pow(t);
+        let sylow_order = 3u64.pow(s);
+        let mut e = 0u64;
+        let mut power = 1u64;
+        for i in 0..s {
+            // b·c^-e has order dividing 3^(s-i), so its 3^(s-1-i)-th power is a cube
+            // root of unity, namely c^(3^(s-1)·digit)
+            let residual = b.mul(c.pow(sylow_order.saturating_sub(e)));
+            let unity = residual.pow(3u64.pow(s.saturating_sub(1).saturating_sub(i)));
+            let c_unity = c.pow(sylow_order / 3);
+            let digit = (0..3u64)
+                .find(|digit| c_unity.pow(*digit) == unity)
+                .expect("c^(3^(s-1)) is a primitive cube root of unity");
+            e = e.saturating_add(digit.saturating_mul(power));
+            power = power.saturating_mul(3);
+        }
+
+        // b is a cube, so 3 | e and x = x₀·c^(-e/3)
+        Some(x0.mul(c.pow(sylow_order.saturating_sub(e / 3))))
+    }
+
+    /// Check if the element is a square, zero included, by Euler's criterion
+    pub fn is_square(&self) -> bool {
+        if self.value == 0 || Q == 2 {
+            return true;
+        }
+
+        let exponent = Q.checked_sub(1).expect("subtraction failed") / 2;
+        self.pow(exponent).value == 1
+    }
+
+    /// A square root of the element, if it has one, the other being its negation
+    pub fn sqrt(&self) -> Option<Self> {
+        if self.value == 0 || Q == 2 {
+            return Some(*self);
+        }
+        if !self.is_square() {
+            return None;
+        }
+
+        // Tonelli-Shanks: q - 1 = 2^s·t with t odd
+        let q_minus_one = Q.checked_sub(1).expect("subtraction failed");
+        let s = q_minus_one.trailing_zeros();
+        let t = q_minus_one >> s;
+
+        let z = (2..Q)
+            .map(Fq::<Q>::new)
+            .find(|z| !z.is_square())
+            .expect("non-squares exist for odd q");
+        let one = Fq::new(1);
+        let mut m = s;
+        let mut c = z.pow(t);
+        let mut x = self.pow(t.saturating_add(1) / 2);
+        let mut b = self.pow(t);
+
+        // x² = a·b, and b lies in a 2-Sylow subgroup that shrinks at every step
+        while b != one {
+            let mut i = 0u32;
+            let mut power = b;
+            while power != one {
+                power = power.mul(power);
+                i = i.saturating_add(1);
+            }
+
+            let g = c.pow(1u64 << m.saturating_sub(i).saturating_sub(1));
+            x = x.mul(g);
+            c = g.mul(g);
+            b = b.mul(c);
+            m = i;
+        }
+
+        Some(x)
+    }
 }
 
 impl<const Q: u64> Add for Fq<Q> {
@@ -320,6 +463,17 @@ mod tests {
         assert_eq!(a.pow(10).value(), 1);
     }
 
+    #[test]
+    fn conditional_select__picks_by_choice() {
+        type F11 = Fq<11>;
+
+        let a = F11::new(3);
+        let b = F11::new(9);
+
+        assert_eq!(F11::conditional_select(&a, &b, false), a);
+        assert_eq!(F11::conditional_select(&a, &b, true), b);
+    }
+
     #[test]
     fn minus_three_square_in_field5() {
         // In F5, -3 ≡ 2 (mod 5)
@@ -342,4 +496,58 @@ mod tests {
         assert!(!Fq::<17>::is_minus_three_square()); // -3 ≡ 14 (mod 17)
         assert!(Fq::<19>::is_minus_three_square()); // -3 ≡ 16 (mod 19)
     }
+
+    #[test]
+    fn cube_root_of_unity__exists_iff_q_is_1_mod_3() {
+        assert_eq!(Fq::<5>::cube_root_of_unity(), None);
+        assert_eq!(Fq::<11>::cube_root_of_unity(), None);
+
+        let omega = Fq::<7>::cube_root_of_unity().unwrap();
+        assert_ne!(omega.value(), 1);
+        assert_eq!(omega.pow(3).value(), 1);
+    }
+
+    #[test]
+    fn cube_root__inverts_cubing() {
+        fn check<const Q: u64>() {
+            for x in (0..Q).map(Fq::<Q>::new) {
+                let cube = x.pow(3);
+                assert_eq!(cube.cube_root().unwrap().pow(3), cube);
+            }
+        }
+
+        // q - 1 = 3^s·t with s = 0, 1, 2 and 3
+        check::<11>();
+        check::<13>();
+        check::<37>();
+        check::<109>();
+
+        // 2 is not a cube mod 7
+        assert_eq!(Fq::<7>::new(2).cube_root(), None);
+    }
+
+    #[test]
+    fn sqrt__inverts_squaring() {
+        fn check<const Q: u64>() {
+            let mut squares = 0u64;
+            for x in (0..Q).map(Fq::<Q>::new) {
+                let square = x.mul(x);
+                assert!(square.is_square());
+                assert_eq!(square.sqrt().unwrap().pow(2), square);
+                if x.is_square() {
+                    squares = squares.saturating_add(1);
+                } else {
+                    assert_eq!(x.sqrt(), None);
+                }
+            }
+            assert_eq!(squares, (Q / 2).saturating_add(1));
+        }
+
+        // q - 1 = 2^s·t with s = 1, 2, 3 and 4
+        check::<11>();
+        check::<13>();
+        check::<41>();
+        check::<97>();
+        check::<2>();
+    }
 }
@@ -0,0 +1,143 @@
+//! Precomputed tables for multiplying a fixed base point
+
+use crate::{
+    curve::TwistedHessianCurve,
+    projective::Projective,
+    scalar::Scalar,
+};
+use alloc::vec::Vec;
+
+/// Table of multiples of a fixed point for fast constant-time k·G
+///
+/// The scalar is split into windows of `window` bits; window i stores
+/// j·2^(window·i)·G for every j < 2^window, so k·G needs one addition per window and
+/// no doublings. Lookups scan a whole row with conditional selects, so the memory
+/// access pattern does not depend on the scalar.
+#[derive(Debug, Clone)]
+pub struct FixedBaseTable<const Q: u64> {
+    curve: TwistedHessianCurve<Q>,
+    window: u32,
+    bits: u32,
+    table: Vec<Projective<Q>>,
+}
+
+impl<const Q: u64> FixedBaseTable<Q> {
+    /// Precompute the table for scalars of up to `bits` bits
+    ///
+    /// The table holds ⌈bits/window⌉·2^window points.
+    pub fn new(
+        curve: &TwistedHessianCurve<Q>,
+        generator: &Projective<Q>,
+        bits: u32,
+        window: u32,
+    ) -> Self {
+        assert!(curve.contains(generator), "Generator must be on the curve");
+        assert!(
+            (1..=8).contains(&window),
+            "Window width must be between 1 and 8"
+        );
+        assert!(bits <= Scalar::BITS, "Scalars are at most 256 bits wide");
+
+        let a = curve.a();
+        let row_len = 1usize << window;
+        let windows = bits.div_ceil(window);
+
+        let mut table = Vec::with_capacity(row_len.saturating_mul(windows as usize));
+        let mut base = *generator;
+        for _ in 0..windows {
+            // row entries are 0·B, 1·B, ..., (2^window - 1)·B
+            let mut multiple = Projective::identity();
+            for _ in 0..row_len {
+                table.push(multiple);
+                multiple = multiple.add(&base, a);
+            }
+            // the next row starts from 2^window·B
+            base = multiple;
+        }
+
+        FixedBaseTable {
+            curve: *curve,
+            window,
+            bits,
+            table,
+        }
+    }
+
+    /// Get the window width in bits
+    pub fn window(&self) -> u32 {
+        self.window
+    }
+
+    /// Get the maximum scalar width in bits
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// Multiply the base point by a scalar
+    pub fn mul(&self, scalar: impl Into<Scalar>) -> Projective<Q> {
+        let scalar = scalar.into();
+        assert!(
+            scalar.bits() <= self.bits,
+            "Scalar must not be wider than the table"
+        );
+
+        let a = self.curve.a();
+        let row_len = 1usize << self.window;
+
+        let mut result = Projective::identity();
+        let mut shift = 0u32;
+        for row in self.table.chunks_exact(row_len) {
+            let digit = scalar.bits_at(shift, self.window);
+            shift = shift.saturating_add(self.window);
+
+            let mut selected = Projective::identity();
+            for (j, entry) in row.iter().enumerate() {
+                selected =
+                    Projective::conditional_select(&selected, entry, digit == j as u64);
+            }
+
+            result = result.add(&selected, a);
+        }
+
+        result
+    }
+}
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::paper_curve_and_generator;
+
+    #[test]
+    fn mul__matches_scalar_mul() {
+        let (curve, generator) = paper_curve_and_generator();
+
+        for window in 1..=5 {
+            let table = FixedBaseTable::new(&curve, &generator, 8, window);
+            for k in 0..256u64 {
+                let expected = curve.scalar_mul(&generator, k);
+                assert!(table.mul(k).is_equal(&expected));
+            }
+        }
+    }
+
+    #[test]
+    fn mul__handles_full_width_scalars() {
+        let (curve, generator) = paper_curve_and_generator();
+        let table = FixedBaseTable::new(&curve, &generator, Scalar::BITS, 4);
+
+        let wide = Scalar::from_limbs([3, 0, 0, 1 << 63]);
+        for k in [Scalar::from(u64::MAX), Scalar::MAX, wide] {
+            let expected = curve.scalar_mul(&generator, k);
+            assert!(table.mul(k).is_equal(&expected));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Scalar must not be wider than the table")]
+    fn mul__should_panic_when_scalar_is_too_wide() {
+        let (curve, generator) = paper_curve_and_generator();
+        FixedBaseTable::new(&curve, &generator, 6, 3).mul(64u64);
+    }
+}
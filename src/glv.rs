@@ -0,0 +1,335 @@
+//! GLV scalar multiplication with the cube-root-of-unity automorphism
+//!
+//! When d = 0 and q ≡ 1 mod 3 (so ω ∈ Fq, see [`Fq::is_minus_three_square`]), the map
+//! φ[X:Y:Z] = [ωX:Y:Z] preserves aX³ + Y³ + Z³ = 0 and fixes the identity [0:-1:1], so
+//! it is a group automorphism of order 3. On a subgroup of prime order n ≡ 1 mod 3 it
+//! acts as multiplication by a root λ of λ² + λ + 1 ≡ 0 mod n, and k·P is computed as
+//! k₁·P + k₂·φ(P) with k ≡ k₁ + k₂λ mod n and |k₁|, |k₂| around √n, halving the
+//! number of doublings.
+//!
+//! The map [X:ωY:ω²Z] does not fix the identity: it is the translation by the
+//! 3-torsion point [0:-ω²:1] (see [`crate::torsion`]), not an endomorphism. Curves
+//! with d ≠ 0 have j-invariant other than 0 and no automorphism of order 3.
+
+use crate::{
+    curve::TwistedHessianCurve,
+    field::Fq,
+    projective::Projective,
+    ring::RingElement,
+    scalar::Scalar,
+};
+use core::ops::Mul;
+
+/// Number of candidates z tried when looking for a cube root of unity z^((n-1)/3)
+/// modulo the order; each one fails with probability 1/3 when n is prime
+const LAMBDA_ATTEMPTS: u64 = 64;
+
+impl<const Q: u64> Projective<Q> {
+    /// Apply the automorphism φ[X:Y:Z] = [ωX:Y:Z] of a curve with d = 0
+    pub fn endomorphism(&self, curve: &TwistedHessianCurve<Q>) -> Self {
+        assert!(
+            curve.d() == RingElement::from_field(Fq::new(0)),
+            "The endomorphism needs a curve with d = 0"
+        );
+        let omega =
+            Fq::<Q>::cube_root_of_unity().expect("The endomorphism needs q ≡ 1 mod 3");
+
+        Projective::new(
+            RingElement::from_field(omega).mul(self.x()),
+            self.y(),
+            self.z(),
+        )
+    }
+}
+
+/// Precomputation for GLV scalar multiplication in the subgroup generated by a point
+#[derive(Debug, Clone, Copy)]
+pub struct Glv<const Q: u64> {
+    curve: TwistedHessianCurve<Q>,
+    order: u64,
+    lambda: u64,
+    /// Short basis (a₁, b₁), (a₂, b₂) of the lattice {(x, y) : x + yλ ≡ 0 mod n}
+    basis: [(i128, i128); 2],
+}
+
+impl<const Q: u64> Glv<Q> {
+    /// Set up GLV for the subgroup generated by `generator` of order `order`
+    ///
+    /// Returns `None` unless d = 0, q ≡ 1 mod 3 and φ acts on the subgroup as some λ
+    /// that is found as a cube root of unity modulo `order`, which needs `order` to be
+    /// a prime ≡ 1 mod 3 in practice.
+    pub fn new(
+        curve: TwistedHessianCurve<Q>,
+        generator: &Projective<Q>,
+        order: u64,
+    ) -> Option<Self> {
+        assert!(curve.contains(generator), "Generator must be on the curve");
+        assert!(
+            curve.scalar_mul(generator, order).is_identity(),
+            "Generator's order must match the provided order"
+        );
+
+        let zero = RingElement::from_field(Fq::new(0));
+        if curve.d() != zero
+            || Fq::<Q>::cube_root_of_unity().is_none()
+            || order.checked_rem(3) != Some(1)
+        {
+            return None;
+        }
+
+        let image = generator.endomorphism(&curve);
+        let exponent = order.saturating_sub(1) / 3;
+        let omega = (2..LAMBDA_ATTEMPTS.min(order))
+            .map(|z| pow_mod(z, exponent, order))
+            .find(|omega| *omega != 1)?;
+        let lambda = [omega, mul_mod(omega, omega, order)]
+            .into_iter()
+            .find(|lambda| curve.scalar_mul(generator, *lambda) == image)?;
+
+        Some(Glv {
+            curve,
+            order,
+            lambda,
+            basis: short_basis(order, lambda),
+        })
+    }
+
+    /// Get the eigenvalue λ with φ(P) = λ·P on the subgroup
+    pub fn lambda(&self) -> u64 {
+        self.lambda
+    }
+
+    /// Get the order of the subgroup
+    pub fn order(&self) -> u64 {
+        self.order
+    }
+
+    /// Split a scalar into k₁ + k₂λ ≡ k mod n with k₁, k₂ around √n
+    pub fn decompose(&self, scalar: impl Into<Scalar>) -> (i128, i128) {
+        let k = scalar
+            .into()
+            .rem(&Scalar::from(self.order))
+            .to_u64()
+            .expect("reduced below a u64 order");
+        let (k, n) = (i128::from(k), i128::from(self.order));
+        let [(a1, b1), (a2, b2)] = self.basis;
+
+        // round(b₂k/n) and round(-b₁k/n), then subtract c₁v₁ + c₂v₂ from (k, 0); all
+        // values are below n², so nothing wraps
+        let round = |x: i128| {
+            x.wrapping_mul(2)
+                .wrapping_add(n)
+                .div_euclid(n.wrapping_mul(2))
+        };
+        let c1 = round(b2.wrapping_mul(k));
+        let c2 = round(b1.wrapping_mul(k).wrapping_neg());
+
+        let k1 = k
+            .wrapping_sub(c1.wrapping_mul(a1))
+            .wrapping_sub(c2.wrapping_mul(a2));
+        let k2 = c1
+            .wrapping_mul(b1)
+            .wrapping_add(c2.wrapping_mul(b2))
+            .wrapping_neg();
+
+        (k1, k2)
+    }
+
+    /// Multiply a point of the subgroup by a scalar, as k₁·P + k₂·φ(P) with one shared
+    /// chain of doublings
+    pub fn scalar_mul(
+        &self,
+        point: &Projective<Q>,
+        scalar: impl Into<Scalar>,
+    ) -> Projective<Q> {
+        let a = self.curve.a();
+        let (k1, k2) = self.decompose(scalar);
+
+        let image = point.endomorphism(&self.curve);
+        let p1 = if k1 < 0 { point.negate() } else { *point };
+        let p2 = if k2 < 0 { image.negate() } else { image };
+        let both = p1.add(&p2, a);
+        let (s1, s2) = (
+            Scalar::from(k1.unsigned_abs()),
+            Scalar::from(k2.unsigned_abs()),
+        );
+
+        let mut result = Projective::identity();
+        for i in (0..s1.bits().max(s2.bits())).rev() {
+            result = result.double(a);
+            match (s1.bit(i), s2.bit(i)) {
+                (true, true) => result = result.add(&both, a),
+                (true, false) => result = result.add(&p1, a),
+                (false, true) => result = result.add(&p2, a),
+                (false, false) => {}
+            }
+        }
+
+        result
+    }
+}
+
+/// Short basis of {(x, y) : x + yλ ≡ 0 mod n} from the extended Euclidean algorithm
+/// on n and λ, following Gallant, Lambert and Vanstone
+fn short_basis(n: u64, lambda: u64) -> [(i128, i128); 2] {
+    let n_wide = i128::from(n);
+
+    // rᵢ = sᵢn + tᵢλ, so (rᵢ, -tᵢ) is in the lattice; stop at the last rₗ ≥ √n
+    let (mut r0, mut r1) = (n_wide, i128::from(lambda));
+    let (mut t0, mut t1) = (0i128, 1i128);
+    while r1.wrapping_mul(r1) >= n_wide {
+        let quotient = r0.checked_div(r1).expect("the remainders stay positive");
+        (r0, r1) = (r1, r0.wrapping_sub(quotient.wrapping_mul(r1)));
+        (t0, t1) = (t1, t0.wrapping_sub(quotient.wrapping_mul(t1)));
+    }
+    let quotient = r0.checked_div(r1).expect("the remainders stay positive");
+    let r2 = r0.wrapping_sub(quotient.wrapping_mul(r1));
+    let t2 = t0.wrapping_sub(quotient.wrapping_mul(t1));
+
+    let norm = |r: i128, t: i128| r.wrapping_mul(r).wrapping_add(t.wrapping_mul(t));
+    let v1 = (r1, t1.wrapping_neg());
+    let v2 = if norm(r0, t0) <= norm(r2, t2) {
+        (r0, t0.wrapping_neg())
+    } else {
+        (r2, t2.wrapping_neg())
+    };
+
+    [v1, v2]
+}
+
+fn mul_mod(a: u64, b: u64, n: u64) -> u64 {
+    let product = u128::from(a).wrapping_mul(u128::from(b));
+    let reduced = product
+        .checked_rem(u128::from(n))
+        .expect("modulus is non-zero");
+    u64::try_from(reduced).expect("reduced below a u64 modulus")
+}
+
+fn pow_mod(base: u64, exponent: u64, n: u64) -> u64 {
+    let mut result = 1u64.checked_rem(n).expect("modulus is non-zero");
+    let mut base = base.checked_rem(n).expect("modulus is non-zero");
+    let mut exp = exponent;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, n);
+        }
+        base = mul_mod(base, base, n);
+        exp >>= 1;
+    }
+
+    result
+}
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::paper_curve_and_generator;
+
+    /// 3X³ + Y³ + Z³ = 0 over F31, with 39 = 3·13 points
+    fn curve_31() -> TwistedHessianCurve<31> {
+        type F31 = Fq<31>;
+
+        let a = RingElement::new(F31::new(3), F31::new(1)); // 3+ε
+        TwistedHessianCurve::new(a, RingElement::from_field(F31::new(0)))
+    }
+
+    /// A point of order 13, lifted from the residue curve and cleared of the other
+    /// factors 3 and 31
+    fn generator_31() -> Projective<31> {
+        let curve = curve_31();
+        let residue = curve.reduce();
+
+        (0..31)
+            .flat_map(|x| (0..31).map(move |y| (x, y)))
+            .map(|(x, y)| {
+                Projective::new(
+                    RingElement::from_field(Fq::new(x)),
+                    RingElement::from_field(Fq::new(y)),
+                    RingElement::from_field(Fq::new(1)),
+                )
+            })
+            .filter(|p| residue.contains(p))
+            .map(|p| curve.scalar_mul(&curve.compose(&p, Fq::new(0)), 93u64))
+            .find(|p| !p.is_identity())
+            .unwrap()
+    }
+
+    #[test]
+    fn endomorphism__is_an_automorphism_of_order_3() {
+        let curve = curve_31();
+        let a = curve.a();
+        let p = generator_31();
+        let q = curve.kernel_point(Fq::new(5)).add(&p.double(a), a);
+
+        let phi = |p: &Projective<31>| p.endomorphism(&curve);
+        assert!(curve.contains(&phi(&p)));
+        assert!(phi(&Projective::identity()).is_identity());
+        assert_eq!(phi(&phi(&phi(&p))), p);
+        assert_eq!(phi(&p.add(&q, a)), phi(&p).add(&phi(&q), a));
+        // φ² + φ + 1 = 0
+        assert!(p.add(&phi(&p), a).add(&phi(&phi(&p)), a).is_identity());
+    }
+
+    #[test]
+    fn new__finds_lambda_on_residue_and_kernel_subgroups() {
+        let curve = curve_31();
+        let p = generator_31();
+
+        let glv = Glv::new(curve, &p, 13).unwrap();
+        let lambda = glv.lambda();
+        assert_eq!(mul_mod(lambda, lambda, 13).wrapping_add(lambda) % 13, 12);
+        assert_eq!(curve.scalar_mul(&p, lambda), p.endomorphism(&curve));
+
+        // on the kernel φ[εt : -1 : 1] = [εωt : -1 : 1], so λ is ω itself
+        let kernel = curve.kernel_point(Fq::new(1));
+        let glv = Glv::new(curve, &kernel, 31).unwrap();
+        assert_eq!(
+            glv.lambda(),
+            Fq::<31>::cube_root_of_unity().unwrap().value()
+        );
+    }
+
+    #[test]
+    fn new__should_reject_unsuitable_curves() {
+        // the paper curve has d ≠ 0, and q = 5 has no cube root of unity
+        let (curve, p) = paper_curve_and_generator();
+        assert!(Glv::new(curve, &p, 45).is_none());
+
+        // 3-torsion is not of the form n ≡ 1 mod 3
+        let curve = curve_31();
+        let torsion = curve.three_torsion_points().nth(1).unwrap();
+        assert!(Glv::new(curve, &torsion, 3).is_none());
+    }
+
+    #[test]
+    #[allow(clippy::arithmetic_side_effects)]
+    fn scalar_mul__matches_double_and_add_proptest() {
+        const P: u64 = 65521;
+        type F = Fq<P>;
+
+        let a = RingElement::new(F::new(2), F::new(7));
+        let curve = TwistedHessianCurve::new(a, RingElement::from_field(F::new(0)));
+        let kernel = curve.kernel_point(F::new(1234));
+        let glv = Glv::new(curve, &kernel, P).unwrap();
+
+        proptest::proptest!(|(k: u64)| {
+            let (k1, k2) = glv.decompose(k);
+            let lambda = i128::from(glv.lambda());
+            assert_eq!(
+                (k1 + k2 * lambda).rem_euclid(i128::from(P)),
+                i128::from(k % P)
+            );
+            assert!(k1.abs() < 512 && k2.abs() < 512);
+
+            assert_eq!(glv.scalar_mul(&kernel, k), kernel.scalar_mul(k, a));
+        });
+
+        let p = generator_31();
+        let glv = Glv::new(curve_31(), &p, 13).unwrap();
+        for k in 0..40u64 {
+            assert_eq!(glv.scalar_mul(&p, k), p.scalar_mul(k, curve_31().a()));
+        }
+    }
+}
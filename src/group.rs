@@ -0,0 +1,269 @@
+//! Group traits and operator overloading for curve points
+
+use crate::{
+    affine::Affine,
+    curve::TwistedHessianCurve,
+    projective::Projective,
+    scalar::Scalar,
+};
+use core::{
+    iter::Sum,
+    ops::{
+        Add,
+        AddAssign,
+        Mul,
+        Neg,
+        Sub,
+    },
+};
+
+/// Abelian group written additively, with scalar multiplication
+///
+/// Addition, subtraction and negation come from the operator traits, so generic
+/// protocol code can be written as `x * g + h`.
+pub trait Group:
+    Sized
+    + Copy
+    + Eq
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + Mul<Scalar, Output = Self>
+    + Sum
+{
+    /// The neutral element
+    fn identity() -> Self;
+
+    /// Check if this is the neutral element
+    fn is_identity(&self) -> bool;
+
+    /// Compute 2·self
+    fn double(&self) -> Self;
+
+    /// Compute k·self
+    #[allow(clippy::arithmetic_side_effects)]
+    fn scalar_mul(&self, scalar: impl Into<Scalar>) -> Self {
+        *self * scalar.into()
+    }
+}
+
+/// A [`Group`] of points on a twisted Hessian curve over Fq[ε]
+pub trait CurveGroup<const Q: u64>: Group {
+    /// Get the point in projective coordinates
+    fn to_projective(&self) -> Projective<Q>;
+
+    /// Normalise the point to its canonical affine representative
+    fn to_affine(&self) -> Affine<Q> {
+        self.to_projective().to_affine()
+    }
+}
+
+/// A point together with the curve it lies on, so arithmetic needs no extra arguments
+///
+/// Every point carries its curve, the identity [0:-1:1] included, and points of
+/// different curves are never equal. As [`Group::identity`] cannot name a curve chosen
+/// at run time, this is not a [`Group`], see [`crate::params::Point`] for curves fixed
+/// at the type level. Mixing points of two different curves panics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CurvePoint<const Q: u64> {
+    point: Projective<Q>,
+    curve: TwistedHessianCurve<Q>,
+}
+
+impl<const Q: u64> CurvePoint<Q> {
+    /// Attach a point to the curve it lies on
+    pub fn new(curve: TwistedHessianCurve<Q>, point: Projective<Q>) -> Self {
+        assert!(curve.contains(&point), "Point must be on the curve");
+
+        CurvePoint { point, curve }
+    }
+
+    /// Get the neutral element of a curve
+    pub fn identity(curve: TwistedHessianCurve<Q>) -> Self {
+        CurvePoint {
+            point: Projective::identity(),
+            curve,
+        }
+    }
+
+    /// Get the underlying projective point
+    pub fn point(&self) -> Projective<Q> {
+        self.point
+    }
+
+    /// Get the curve
+    pub fn curve(&self) -> TwistedHessianCurve<Q> {
+        self.curve
+    }
+
+    /// Check if this is the neutral element
+    pub fn is_identity(&self) -> bool {
+        self.point.is_identity()
+    }
+
+    /// Compute 2·self
+    pub fn double(&self) -> Self {
+        self.with_point(self.point.double(self.curve.a()))
+    }
+
+    /// Compute k·self
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn scalar_mul(&self, scalar: impl Into<Scalar>) -> Self {
+        *self * scalar.into()
+    }
+
+    /// Normalise the point to its canonical affine representative
+    pub fn to_affine(&self) -> Affine<Q> {
+        self.point.to_affine()
+    }
+
+    fn with_point(&self, point: Projective<Q>) -> Self {
+        CurvePoint {
+            point,
+            curve: self.curve,
+        }
+    }
+}
+
+impl<const Q: u64> Add for CurvePoint<Q> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        assert!(self.curve == rhs.curve, "Points must lie on the same curve");
+
+        self.with_point(self.point.add(&rhs.point, self.curve.a()))
+    }
+}
+
+#[allow(clippy::arithmetic_side_effects)]
+impl<const Q: u64> AddAssign for CurvePoint<Q> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const Q: u64> Neg for CurvePoint<Q> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.with_point(self.point.negate())
+    }
+}
+
+#[allow(clippy::arithmetic_side_effects)]
+impl<const Q: u64> Sub for CurvePoint<Q> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl<const Q: u64> Mul<Scalar> for CurvePoint<Q> {
+    type Output = Self;
+
+    /// Variable-time double-and-add, see [`Projective::scalar_mul`]
+    fn mul(self, rhs: Scalar) -> Self {
+        self.with_point(self.point.scalar_mul(rhs, self.curve.a()))
+    }
+}
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        field::Fq,
+        ring::RingElement,
+        test_support::paper_curve_and_generator,
+    };
+
+    fn paper_generator() -> CurvePoint<5> {
+        let (curve, generator) = paper_curve_and_generator();
+
+        CurvePoint::new(curve, generator)
+    }
+
+    fn other_curve() -> TwistedHessianCurve<5> {
+        type F5 = Fq<5>;
+
+        TwistedHessianCurve::new(
+            RingElement::new(F5::new(1), F5::new(1)),
+            RingElement::new(F5::new(1), F5::new(2)),
+        )
+    }
+
+    #[test]
+    #[allow(clippy::arithmetic_side_effects)]
+    fn operators__match_projective_arithmetic() {
+        let g = paper_generator();
+        let identity = CurvePoint::identity(g.curve());
+
+        assert_eq!((g + g).point(), g.point().double(g.curve().a()));
+        assert_eq!(g.double(), g + g);
+        assert_eq!(g - g, identity);
+        assert_eq!(-g + g.scalar_mul(2u64), g);
+        assert_eq!(g * Scalar::from(45u64), identity);
+        assert_eq!(g.to_affine(), g.point().to_affine());
+
+        let mut acc = g;
+        acc += g;
+        acc += identity;
+        assert_eq!(acc, g.scalar_mul(2u64));
+    }
+
+    #[test]
+    #[allow(clippy::arithmetic_side_effects)]
+    fn identity__is_neutral_on_its_curve() {
+        let g = paper_generator();
+        let identity = CurvePoint::identity(g.curve());
+
+        assert_eq!(identity.curve(), g.curve());
+        assert!(identity.double().is_identity());
+        assert!((identity * Scalar::from(7u64)).is_identity());
+        assert_eq!(identity + g, g);
+        assert_eq!(identity - g, -g);
+    }
+
+    #[test]
+    #[allow(clippy::arithmetic_side_effects)]
+    fn add__kats_paper_3_1() {
+        let g = paper_generator();
+
+        // P + 2P + ... + 9P = 45P = O
+        let total = (1..=9u64)
+            .map(|k| g.scalar_mul(k))
+            .fold(CurvePoint::identity(g.curve()), |acc, p| acc + p);
+        assert!(total.is_identity());
+    }
+
+    #[test]
+    #[allow(clippy::arithmetic_side_effects)]
+    #[should_panic(expected = "Points must lie on the same curve")]
+    fn add__should_panic_when_curves_differ() {
+        let g = paper_generator();
+
+        let _ = g + CurvePoint::identity(other_curve());
+    }
+
+    #[test]
+    fn eq__is_transitive_and_agrees_with_hash() {
+        use std::collections::HashSet;
+
+        let g = paper_generator();
+        let identity = CurvePoint::identity(g.curve());
+        let other_identity = CurvePoint::identity(other_curve());
+        let killed = g.scalar_mul(45u64);
+
+        // the identities of two curves are the same projective point, but neither is
+        // equal to the other, so no chain of equalities can link them
+        assert_eq!(identity.point(), other_identity.point());
+        assert_ne!(identity, other_identity);
+        assert_eq!(killed, identity);
+        assert_ne!(killed, other_identity);
+
+        let set: HashSet<_> = [identity, killed, other_identity].into_iter().collect();
+        assert_eq!(set.len(), 2);
+    }
+}
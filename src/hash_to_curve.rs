@@ -0,0 +1,387 @@
+//! Hashing to twisted Hessian curves over Fq[ε], after RFC 9380
+//!
+//! Messages are expanded with `expand_message_xmd` over SHA-256 and reduced to
+//! elements u = u₀ + u₁ε of Fq[ε], each made of two field elements with 128 bits of
+//! extra length to keep the bias negligible. The map to the curve sends u₀ through
+//! the Shallue–van de Woestijne map of the Weierstrass model of the residue curve,
+//! pulls the result back to a point of the residue curve, and lifts it with ε-part
+//! parameter u₁, see [`TwistedHessianCurve::lift_point`].
+//!
+//! `hash_to_curve` adds the maps of two field elements, which makes the output
+//! indistinguishable from a random point, while `encode_to_curve` maps a single one
+//! and only reaches a fraction of the points. Both clear the cofactor at the end, so
+//! the result lies in the prime-order subgroup.
+
+use crate::{
+    curve::TwistedHessianCurve,
+    field::Fq,
+    projective::Projective,
+    ring::RingElement,
+    scalar::Scalar,
+    sha256::{
+        BLOCK_SIZE,
+        DIGEST_SIZE,
+        Sha256,
+    },
+    weierstrass::{
+        WeierstrassCurve,
+        WeierstrassPoint,
+    },
+};
+use alloc::vec::Vec;
+use core::ops::{
+    Add,
+    Mul,
+    Sub,
+};
+
+/// Target security level k in bits, which sets the length of the hashed field elements
+pub const SECURITY_BITS: u32 = 128;
+
+/// Expand a message into `len_in_bytes` uniform bytes with SHA-256, RFC 9380 §5.3.1
+pub fn expand_message_xmd(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Vec<u8> {
+    let ell = len_in_bytes.div_ceil(DIGEST_SIZE);
+    assert!(ell <= 255, "Requested too many bytes");
+    assert!(len_in_bytes <= 65535, "Requested too many bytes");
+    assert!(
+        !dst.is_empty() && dst.len() <= 255,
+        "Domain separation tag must have 1 to 255 bytes"
+    );
+
+    let dst_length = [u8::try_from(dst.len()).expect("checked above")];
+    let length = u16::try_from(len_in_bytes)
+        .expect("checked above")
+        .to_be_bytes();
+
+    // b₀ = H(Z_pad || msg || l_i_b_str || 0 || DST_prime)
+    let mut hasher = Sha256::new();
+    hasher.update(&[0; BLOCK_SIZE]);
+    hasher.update(msg);
+    hasher.update(&length);
+    hasher.update(&[0]);
+    hasher.update(dst);
+    hasher.update(&dst_length);
+    let b0 = hasher.finalize();
+
+    // bᵢ = H((b₀ xor bᵢ₋₁) || i || DST_prime), with b₁ = H(b₀ || 1 || DST_prime)
+    let mut uniform = Vec::with_capacity(ell.saturating_mul(DIGEST_SIZE));
+    let mut previous = [0u8; DIGEST_SIZE];
+    for i in 1..=ell {
+        let mut block = b0;
+        for (byte, prev) in block.iter_mut().zip(previous) {
+            *byte ^= prev;
+        }
+
+        let mut hasher = Sha256::new();
+        hasher.update(&block);
+        hasher.update(&[u8::try_from(i).expect("ell is at most 255")]);
+        hasher.update(dst);
+        hasher.update(&dst_length);
+        previous = hasher.finalize();
+        uniform.extend_from_slice(&previous);
+    }
+
+    uniform.truncate(len_in_bytes);
+    uniform
+}
+
+/// Hash a message to `count` elements of Fq[ε], RFC 9380 §5.2 with extension degree 2
+pub fn hash_to_field<const Q: u64>(
+    msg: &[u8],
+    dst: &[u8],
+    count: usize,
+) -> Vec<RingElement<Q>> {
+    // L = ⌈(⌈log₂ q⌉ + k)/8⌉ bytes per field element
+    let bits = u64::BITS.saturating_sub(Q.leading_zeros());
+    let element_length = usize::try_from(bits.saturating_add(SECURITY_BITS).div_ceil(8))
+        .expect("a few dozen bytes");
+    let uniform = expand_message_xmd(
+        msg,
+        dst,
+        count.saturating_mul(2).saturating_mul(element_length),
+    );
+
+    let elements: Vec<Fq<Q>> = uniform
+        .chunks_exact(element_length)
+        .map(|chunk| {
+            // OS2IP(chunk) mod q, by Horner's rule
+            let value = chunk.iter().fold(0u128, |acc, byte| {
+                acc.checked_mul(256)
+                    .and_then(|acc| acc.checked_add(u128::from(*byte)))
+                    .and_then(|acc| acc.checked_rem(u128::from(Q)))
+                    .expect("acc < q < 2⁶⁴")
+            });
+            Fq::new(u64::try_from(value).expect("reduced modulo q"))
+        })
+        .collect();
+
+    elements
+        .chunks_exact(2)
+        .map(|pair| RingElement::new(pair[0], pair[1]))
+        .collect()
+}
+
+impl<const Q: u64> WeierstrassCurve<Q> {
+    /// Map a field element to the curve with the Shallue–van de Woestijne method, RFC
+    /// 9380 §6.6.1
+    ///
+    /// Always gives an affine point. It needs q > 3 and a constant Z as in RFC 9380
+    /// §H.1, which exists except for a few curves over tiny fields.
+    pub fn map_to_curve_svdw(&self, u: Fq<Q>) -> WeierstrassPoint<Q> {
+        assert!(
+            self.a().epsilon_coeff().value() == 0
+                && self.b().epsilon_coeff().value() == 0,
+            "The SvdW map needs a curve over Fq"
+        );
+
+        let (a, b) = (self.a().constant(), self.b().constant());
+        let g = |x: Fq<Q>| x.mul(x).mul(x).add(a.mul(x)).add(b);
+        let (zero, one) = (Fq::new(0), Fq::new(1));
+        let (z, c1, c2, c3, c4) = svdw_constants(a, b);
+
+        let tv1 = u.mul(u).mul(c1);
+        let tv2 = one.add(tv1);
+        let tv1 = one.sub(tv1);
+        let tv3 = inv0(tv1.mul(tv2));
+        let tv4 = u.mul(tv1).mul(tv3).mul(c3);
+
+        let x1 = c2.sub(tv4);
+        let e1 = g(x1).is_square();
+        let x2 = c2.add(tv4);
+        let e2 = g(x2).is_square() && !e1;
+        let x3 = tv2.mul(tv2).mul(tv3);
+        let x3 = x3.mul(x3).mul(c4).add(z);
+
+        let x = Fq::conditional_select(&x3, &x1, e1);
+        let x = Fq::conditional_select(&x, &x2, e2);
+        let y = g(x).sqrt().expect("one of g(x₁), g(x₂), g(x₃) is a square");
+        let y = Fq::conditional_select(&zero.sub(y), &y, sgn0(u) == sgn0(y));
+
+        WeierstrassPoint::from_affine(
+            RingElement::from_field(x),
+            RingElement::from_field(y),
+        )
+    }
+}
+
+impl<const Q: u64> TwistedHessianCurve<Q> {
+    /// Map an element u₀ + u₁ε to the curve, as the lift with parameter u₁ of the SvdW
+    /// image of u₀ on the residue curve
+    pub fn map_to_curve(&self, u: RingElement<Q>) -> Projective<Q> {
+        let residue = self.reduce();
+        let point = residue.to_weierstrass().map_to_curve_svdw(u.constant());
+
+        self.lift_point(
+            &Projective::from_weierstrass(&point, &residue),
+            u.epsilon_coeff(),
+        )
+    }
+
+    /// Hash a message to a point of the subgroup of index `cofactor`, uniformly
+    /// distributed in it
+    pub fn hash_to_curve(
+        &self,
+        msg: &[u8],
+        dst: &[u8],
+        cofactor: impl Into<Scalar>,
+    ) -> Projective<Q> {
+        let u = hash_to_field::<Q>(msg, dst, 2);
+        let sum = self.add(&self.map_to_curve(u[0]), &self.map_to_curve(u[1]));
+
+        self.clear_cofactor(&sum, cofactor)
+    }
+
+    /// Encode a message as a point of the subgroup of index `cofactor`, which is
+    /// cheaper than [`TwistedHessianCurve::hash_to_curve`] but not uniform
+    pub fn encode_to_curve(
+        &self,
+        msg: &[u8],
+        dst: &[u8],
+        cofactor: impl Into<Scalar>,
+    ) -> Projective<Q> {
+        let u = hash_to_field::<Q>(msg, dst, 1);
+
+        self.clear_cofactor(&self.map_to_curve(u[0]), cofactor)
+    }
+}
+
+/// The constants Z, c₁ = g(Z), c₂ = -Z/2, c₃ = √(-g(Z)(3Z² + 4A)) with sgn0(c₃) = 0
+/// and c₄ = -4g(Z)/(3Z² + 4A) of the SvdW map, Z being found as in RFC 9380 §H.1
+fn svdw_constants<const Q: u64>(
+    a: Fq<Q>,
+    b: Fq<Q>,
+) -> (Fq<Q>, Fq<Q>, Fq<Q>, Fq<Q>, Fq<Q>) {
+    assert!(Q > 3, "The SvdW map needs q > 3");
+
+    let zero = Fq::new(0);
+    let (two, three, four) = (Fq::new(2), Fq::new(3), Fq::new(4));
+    let g = |x: Fq<Q>| x.mul(x).mul(x).add(a.mul(x)).add(b);
+    // 3Z² + 4A, which h(Z) = -(3Z² + 4A)/(4g(Z)) needs to be non-zero
+    let t = |z: Fq<Q>| three.mul(z).mul(z).add(four.mul(a));
+
+    let z = (1..Q)
+        .flat_map(|ctr| [Fq::new(ctr), zero.sub(Fq::new(ctr))])
+        .find(|&z| {
+            let (gz, tz) = (g(z), t(z));
+            if gz.value() == 0 || tz.value() == 0 {
+                return false;
+            }
+            let h = zero.sub(tz.mul(four.mul(gz).inv()));
+
+            h.is_square() && (gz.is_square() || g(zero.sub(z.mul(two.inv()))).is_square())
+        })
+        .expect("No SvdW constant Z exists for this curve");
+
+    let (gz, tz) = (g(z), t(z));
+    let c3 = zero
+        .sub(gz.mul(tz))
+        .sqrt()
+        .expect("h(Z) is a square, and so is -g(Z)(3Z² + 4A) = 4g(Z)²h(Z)");
+    let c3 = Fq::conditional_select(&c3, &zero.sub(c3), sgn0(c3) == 1);
+
+    (
+        z,
+        gz,
+        zero.sub(z.mul(two.inv())),
+        c3,
+        zero.sub(four.mul(gz).mul(tz.inv())),
+    )
+}
+
+/// The parity of an element, RFC 9380 §4.1
+fn sgn0<const Q: u64>(x: Fq<Q>) -> u64 {
+    x.value() & 1
+}
+
+/// The inverse, or 0 for 0
+fn inv0<const Q: u64>(x: Fq<Q>) -> Fq<Q> {
+    if x.value() == 0 { x } else { x.inv() }
+}
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::paper_curve;
+
+    const DST: &[u8] = b"QUUX-V01-CS02-with-expander-SHA256-128";
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    #[test]
+    fn expand_message_xmd__rfc_9380_kats() {
+        assert_eq!(
+            hex(&expand_message_xmd(b"", DST, 0x20)),
+            "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235"
+        );
+        assert_eq!(
+            hex(&expand_message_xmd(b"abc", DST, 0x20)),
+            "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615"
+        );
+        assert_eq!(
+            hex(&expand_message_xmd(b"abc", DST, 0x80)),
+            "abba86a6129e366fc877aab32fc4ffc70120d8996c88aee2fe4b32d6c7b6437a\
+             647e6c3163d40b76a73cf6a5674ef1d890f95b664ee0afa5359a5c4e07985635\
+             bbecbac65d747d3d2da7ec2b8221b17b0ca9dc8a1ac1c07ea6a1e60583e2cb00\
+             058e77b7b72a298425cd1b941ad4ec65e8afc50303a22c0f99b0509b4c895f40"
+        );
+    }
+
+    #[test]
+    fn map_to_curve_svdw__lands_on_the_curve_for_every_input() {
+        fn check<const Q: u64>(a: u64, d: u64) {
+            let curve = TwistedHessianCurve::<Q>::new(
+                RingElement::from_field(Fq::new(a)),
+                RingElement::from_field(Fq::new(d)),
+            );
+            let weierstrass = curve.to_weierstrass();
+
+            for u in (0..Q).map(Fq::new) {
+                let point = weierstrass.map_to_curve_svdw(u);
+                assert!(weierstrass.contains(&point) && !point.is_identity());
+
+                // the sign of y follows u, unless y = 0
+                let (_, y) = point.to_affine().unwrap();
+                assert!(y.constant().value() == 0 || sgn0(y.constant()) == sgn0(u));
+            }
+        }
+
+        check::<5>(1, 1);
+        check::<7>(1, 2);
+        check::<11>(2, 0);
+        check::<13>(2, 5);
+        check::<101>(3, 7);
+    }
+
+    #[test]
+    fn map_to_curve__is_deterministic_and_on_the_curve() {
+        const Q: u64 = 2_147_483_647;
+
+        let a = RingElement::new(Fq::new(2), Fq::new(7));
+        let d = RingElement::new(Fq::new(5), Fq::new(3));
+        let curve = TwistedHessianCurve::<Q>::new(a, d);
+
+        for u in hash_to_field::<Q>(b"abc", DST, 8) {
+            let point = curve.map_to_curve(u);
+            assert!(curve.contains(&point));
+            assert_eq!(point, curve.map_to_curve(u));
+        }
+    }
+
+    #[test]
+    fn hash_to_curve__lands_in_the_subgroup() {
+        // E(F5[ε]) is cyclic of order 45, so clearing the cofactor 5 leaves order 9
+        let curve = paper_curve();
+
+        for msg in [&b""[..], b"abc", b"abcdef0123456789", &[0x61; 1000]] {
+            let hashed = curve.hash_to_curve(msg, DST, 5u64);
+            let encoded = curve.encode_to_curve(msg, DST, 5u64);
+
+            assert!(curve.is_in_subgroup(&hashed, 9u64));
+            assert!(curve.is_in_subgroup(&encoded, 9u64));
+            assert_eq!(hashed, curve.hash_to_curve(msg, DST, 5u64));
+        }
+    }
+
+    #[test]
+    fn hash_to_curve__depends_on_the_domain_separation_tag() {
+        const Q: u64 = 2_147_483_647;
+
+        let a = RingElement::new(Fq::new(2), Fq::new(7));
+        let d = RingElement::new(Fq::new(5), Fq::new(3));
+        let curve = TwistedHessianCurve::<Q>::new(a, d);
+
+        let first = curve.hash_to_curve(b"abc", b"protocol-A", 1u64);
+        let second = curve.hash_to_curve(b"abc", b"protocol-B", 1u64);
+        assert!(curve.contains(&first) && curve.contains(&second));
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn map_to_curve__lifts_along_the_epsilon_part() {
+        let curve = paper_curve();
+        let residue = curve.reduce();
+
+        for u0 in (0..5).map(Fq::new) {
+            let lifts: Vec<_> = (0..5)
+                .map(|u1| curve.map_to_curve(RingElement::new(u0, Fq::new(u1))))
+                .collect();
+
+            // same point of the residue curve, and all q points above it
+            for (i, lift) in lifts.iter().enumerate() {
+                assert!(curve.contains(lift));
+                assert!(residue.contains(&lift.reduce()));
+                assert_eq!(lift.reduce(), lifts[0].reduce());
+                assert!(lifts[..i].iter().all(|other| other != lift));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Domain separation tag must have 1 to 255 bytes")]
+    fn expand_message_xmd__should_panic_when_dst_is_empty() {
+        expand_message_xmd(b"abc", b"", 32);
+    }
+}
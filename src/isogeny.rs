@@ -0,0 +1,398 @@
+//! 3-isogenies between twisted Hessian curves
+//!
+//! On aX³ + Y³ + Z³ = dXYZ the forms u = Y³, v = Z³ and w = aX³ are invariant under
+//! translation by the 3-torsion points [0 : -ζ : 1], ζ³ = 1, and satisfy
+//! (u + v + w)³ = (d³/a)·uvw. The discrete Fourier transform
+//!
+//! X' = ω²(u + v + w), Y' = ωu + v + ω²w, Z' = u + ωv + ω²w,
+//!
+//! with u + v + w = dXYZ, turns this into the twisted Hessian curve
+//! a'X'³ + Y'³ + Z'³ = 3X'Y'Z' with a' = 1 - 27a/d³, and the powers of ω are chosen so
+//! that the identity goes to the identity. The result is a 3-isogeny with kernel
+//! {[0:-1:1], [0:-ω:1], [0:-ω²:1]}.
+//!
+//! This needs ω ∈ Fq, i.e. q ≡ 1 mod 3, and d to be a unit. Over Fq[ε] the map is
+//! given by the same forms, so it reduces modulo ε to the isogeny of residue curves.
+//! Applying it twice lands on a' = 27a/d³, d' = 3, which (X:Y:Z) ↦ (3X/d : Y : Z) maps
+//! back to the original curve, the composition being multiplication by ±3.
+//!
+//! The other three kernels of order 3, {O, [-1/β:1:0], [-1/β:0:1]} for β³ = a, are
+//! reduced to this one. Translation by [-1/β:1:0] is the cyclic permutation
+//! (βX, Y, Z) ↦ (Y, Z, βX), see [`TwistedHessianCurve::translate`], which the change
+//! of coordinates ψ = (βX + Y + Z, βX + ωY + ω²Z, βX + ω²Y + ωZ) diagonalises. As
+//! ψ(O) = O and the kernel goes to {[0:-ζ:1]}, ψ is an isomorphism onto the Hessian
+//! curve U³ + V³ + W³ = d'UVW with d' = 3(d₁ + 6)/(d₁ - 3), d₁ = d/β, which follows
+//! from UVW = S - 3P and U³ + V³ + W³ = 3S + 18P for S = (βX)³ + Y³ + Z³ = d₁P and
+//! P = βXYZ.
+
+use crate::{
+    curve::TwistedHessianCurve,
+    field::Fq,
+    projective::Projective,
+    ring::RingElement,
+};
+use core::ops::{
+    Add,
+    Mul,
+    Sub,
+};
+
+/// A 3-isogeny of a twisted Hessian curve, by default with kernel
+/// {[0:-1:1], [0:-ω:1], [0:-ω²:1]}
+#[derive(Debug, Clone, Copy)]
+pub struct ThreeIsogeny<const Q: u64> {
+    domain: TwistedHessianCurve<Q>,
+    codomain: TwistedHessianCurve<Q>,
+    omega: RingElement<Q>,
+    /// The curve whose kernel {[0:-ζ:1]} is mapped, the domain itself unless the
+    /// kernel is {O, [-1/β:1:0], [-1/β:0:1]} and ψ changes coordinates
+    hessian: TwistedHessianCurve<Q>,
+    beta: Option<RingElement<Q>>,
+}
+
+impl<const Q: u64> ThreeIsogeny<Q> {
+    /// Set up the isogeny from `domain`, or `None` unless q ≡ 1 mod 3 and d is a unit
+    pub fn new(domain: TwistedHessianCurve<Q>) -> Option<Self> {
+        let omega = RingElement::from_field(Fq::<Q>::cube_root_of_unity()?);
+        let codomain = standard_codomain(&domain)?;
+
+        Some(ThreeIsogeny {
+            domain,
+            codomain,
+            omega,
+            hessian: domain,
+            beta: None,
+        })
+    }
+
+    /// Set up the isogeny from `domain` whose kernel is generated by a point of order 3,
+    /// or `None` unless q ≡ 1 mod 3 and d is a unit, or d' after the change of
+    /// coordinates ψ, which fails for d = -6β
+    pub fn with_kernel(
+        domain: TwistedHessianCurve<Q>,
+        generator: &Projective<Q>,
+    ) -> Option<Self> {
+        assert!(
+            !generator.is_identity()
+                && domain.three_torsion_points().any(|t| t == *generator),
+            "Kernel must be generated by a point of order 3"
+        );
+
+        let zero = RingElement::from_field(Fq::new(0));
+        if generator.x() == zero {
+            return ThreeIsogeny::new(domain);
+        }
+
+        // [-1/β : 1 : 0] or its negation [-1/β : 0 : 1]
+        let beta = zero.sub(generator.y().add(generator.z()).mul(generator.x().inv()));
+        let omega = RingElement::from_field(Fq::<Q>::cube_root_of_unity()?);
+
+        // d' = 3(d₁ + 6)/(d₁ - 3), the denominator being a unit as the domain is smooth
+        let three = RingElement::from_field(Fq::new(3));
+        let six = RingElement::from_field(Fq::new(6));
+        let d1 = domain.d().mul(beta.inv());
+        let d = three.mul(d1.add(six)).mul(d1.sub(three).inv());
+        let hessian = TwistedHessianCurve::new(RingElement::from_field(Fq::new(1)), d);
+
+        Some(ThreeIsogeny {
+            domain,
+            codomain: standard_codomain(&hessian)?,
+            omega,
+            hessian,
+            beta: Some(beta),
+        })
+    }
+
+    /// Get the domain curve
+    pub fn domain(&self) -> TwistedHessianCurve<Q> {
+        self.domain
+    }
+
+    /// Get the 3-isogenous curve a'X³ + Y³ + Z³ = 3XYZ
+    pub fn codomain(&self) -> TwistedHessianCurve<Q> {
+        self.codomain
+    }
+
+    /// Get the points of the kernel, the identity first
+    pub fn kernel(&self) -> [Projective<Q>; 3] {
+        let zero = RingElement::from_field(Fq::new(0));
+        let one = RingElement::from_field(Fq::new(1));
+        let omega = self.omega;
+
+        match self.beta {
+            Some(beta) => {
+                let x = zero.sub(beta.inv());
+                [
+                    Projective::identity(),
+                    Projective::new(x, one, zero),
+                    Projective::new(x, zero, one),
+                ]
+            }
+            None => [one, omega, omega.mul(omega)]
+                .map(|zeta| Projective::new(zero, zero.sub(zeta), one)),
+        }
+    }
+
+    /// Map a point of the domain to the codomain
+    pub fn apply(&self, point: &Projective<Q>) -> Projective<Q> {
+        assert!(self.domain.contains(point), "Point must be on the curve");
+
+        let point = self.change_coordinates(point);
+        let (x, y, z) = (point.x(), point.y(), point.z());
+        let omega = self.omega;
+        let omega_squared = omega.mul(omega);
+
+        let u = y.mul(y).mul(y);
+        let v = z.mul(z).mul(z);
+        let w = self.hessian.a().mul(x).mul(x).mul(x);
+
+        Projective::new(
+            omega_squared.mul(self.hessian.d()).mul(x).mul(y).mul(z),
+            omega.mul(u).add(v).add(omega_squared.mul(w)),
+            u.add(omega.mul(v)).add(omega_squared.mul(w)),
+        )
+    }
+
+    /// Get the isogeny of the codomain whose composition with this one is ±3 after
+    /// [`ThreeIsogeny::to_domain`]
+    pub fn next(&self) -> Self {
+        ThreeIsogeny::new(self.codomain).expect("the codomain has d = 3")
+    }
+
+    /// Map a point of [`ThreeIsogeny::next`]'s codomain back to the domain
+    ///
+    /// With a and d those of the curve carrying the kernel {[0:-ζ:1]}, that codomain
+    /// is a = 27a/d³ and d = 3, and (X:Y:Z) ↦ (3X/d : Y : Z) maps it to that curve,
+    /// followed by ψ⁻¹ when the coordinates were changed.
+    pub fn to_domain(&self, point: &Projective<Q>) -> Projective<Q> {
+        let three = RingElement::from_field(Fq::new(3));
+        let point = Projective::new(
+            three.mul(self.hessian.d().inv()).mul(point.x()),
+            point.y(),
+            point.z(),
+        );
+
+        let Some(beta) = self.beta else {
+            return point;
+        };
+
+        // ψ⁻¹ up to the factor 3
+        let (u, v, w) = (point.x(), point.y(), point.z());
+        let omega = self.omega;
+        let omega_squared = omega.mul(omega);
+        Projective::new(
+            u.add(v).add(w).mul(beta.inv()),
+            u.add(omega_squared.mul(v)).add(omega.mul(w)),
+            u.add(omega.mul(v)).add(omega_squared.mul(w)),
+        )
+    }
+
+    /// Change coordinates by ψ when the kernel is not {[0:-ζ:1]}
+    fn change_coordinates(&self, point: &Projective<Q>) -> Projective<Q> {
+        let Some(beta) = self.beta else {
+            return *point;
+        };
+
+        let x = beta.mul(point.x());
+        let (y, z) = (point.y(), point.z());
+        let omega = self.omega;
+        let omega_squared = omega.mul(omega);
+        Projective::new(
+            x.add(y).add(z),
+            x.add(omega.mul(y)).add(omega_squared.mul(z)),
+            x.add(omega_squared.mul(y)).add(omega.mul(z)),
+        )
+    }
+}
+
+/// The curve a' = 1 - 27a/d³, d' = 3 isogenous to `curve` by the kernel {[0:-ζ:1]}, or
+/// `None` unless d is a unit
+fn standard_codomain<const Q: u64>(
+    curve: &TwistedHessianCurve<Q>,
+) -> Option<TwistedHessianCurve<Q>> {
+    let d = curve.d();
+    if !d.is_invertible() {
+        return None;
+    }
+
+    let one = RingElement::from_field(Fq::new(1));
+    let twenty_seven = RingElement::from_field(Fq::new(27));
+    let a = one.sub(twenty_seven.mul(curve.a()).mul(d.mul(d).mul(d).inv()));
+
+    Some(TwistedHessianCurve::new(
+        a,
+        RingElement::from_field(Fq::new(3)),
+    ))
+}
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{
+        all_points,
+        paper_curve,
+    };
+
+    fn curve_7() -> TwistedHessianCurve<7> {
+        type F7 = Fq<7>;
+
+        let a = RingElement::new(F7::new(1), F7::new(1)); // 1+ε
+        let d = RingElement::new(F7::new(2), F7::new(1)); // 2+ε
+        TwistedHessianCurve::new(a, d)
+    }
+
+    #[test]
+    fn apply__is_a_homomorphism_with_the_expected_kernel() {
+        let curve = curve_7();
+        let isogeny = ThreeIsogeny::new(curve).unwrap();
+        let codomain = isogeny.codomain();
+        let points = all_points(&curve);
+
+        for t in isogeny.kernel() {
+            assert!(isogeny.apply(&t).is_identity());
+        }
+        let kernel_size = points
+            .iter()
+            .filter(|p| isogeny.apply(p).is_identity())
+            .count();
+        assert_eq!(kernel_size, 3);
+
+        for p in &points {
+            let image = isogeny.apply(p);
+            assert!(codomain.contains(&image));
+
+            for q in points.iter().step_by(5) {
+                assert_eq!(
+                    isogeny.apply(&curve.add(p, q)),
+                    codomain.add(&image, &isogeny.apply(q))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn next__composes_to_multiplication_by_3() {
+        let curve = curve_7();
+        let first = ThreeIsogeny::new(curve).unwrap();
+        let second = first.next();
+
+        for p in all_points(&curve) {
+            let back = first.to_domain(&second.apply(&first.apply(&p)));
+            let tripled = p.triple(curve.a());
+
+            assert!(curve.contains(&back));
+            assert!(back == tripled || back == tripled.negate());
+        }
+    }
+
+    #[test]
+    fn apply__reduces_to_the_isogeny_of_residue_curves() {
+        let curve = curve_7();
+        let isogeny = ThreeIsogeny::new(curve).unwrap();
+        let residue = ThreeIsogeny::new(curve.reduce()).unwrap();
+
+        assert_eq!(residue.codomain(), isogeny.codomain().reduce());
+        for p in all_points(&curve) {
+            assert_eq!(isogeny.apply(&p).reduce(), residue.apply(&p.reduce()));
+        }
+    }
+
+    #[test]
+    fn with_kernel__gives_the_four_isogenies_of_degree_3() {
+        type F13 = Fq<13>;
+
+        // a₀ = 1 is a cube, and d₀ = 2 is not -6β for any of its cube roots β
+        let a = RingElement::new(F13::new(1), F13::new(1)); // 1+ε
+        let d = RingElement::new(F13::new(2), F13::new(1)); // 2+ε
+        let curve = TwistedHessianCurve::new(a, d);
+        let points = all_points(&curve);
+
+        // the eight points of order 3 generate four kernels, each twice
+        let mut codomains = Vec::new();
+        for generator in curve.three_torsion_points().skip(1) {
+            let isogeny = ThreeIsogeny::with_kernel(curve, &generator).unwrap();
+            let codomain = isogeny.codomain();
+            let kernel = isogeny.kernel();
+            assert!(kernel.contains(&generator) && kernel.contains(&generator.negate()));
+
+            let image: Vec<_> = points.iter().map(|p| isogeny.apply(p)).collect();
+            let in_kernel: Vec<_> = points
+                .iter()
+                .zip(&image)
+                .filter(|(_, q)| q.is_identity())
+                .map(|(p, _)| *p)
+                .collect();
+            assert_eq!(in_kernel.len(), 3);
+            assert!(kernel.iter().all(|t| in_kernel.contains(t)));
+
+            for (p, p_image) in points.iter().zip(&image).step_by(3) {
+                assert!(codomain.contains(p_image));
+                for (q, q_image) in points.iter().zip(&image).step_by(7) {
+                    assert_eq!(
+                        isogeny.apply(&curve.add(p, q)),
+                        codomain.add(p_image, q_image)
+                    );
+                }
+
+                let back = isogeny.to_domain(&isogeny.next().apply(p_image));
+                let tripled = p.triple(curve.a());
+                assert!(back == tripled || back == tripled.negate());
+            }
+
+            if !codomains.contains(&codomain) {
+                codomains.push(codomain);
+            }
+        }
+        assert_eq!(codomains.len(), 4);
+    }
+
+    #[test]
+    fn with_kernel__matches_new_for_the_kernel_on_x_0() {
+        let curve = curve_7();
+        let isogeny = ThreeIsogeny::new(curve).unwrap();
+        let generated = ThreeIsogeny::with_kernel(curve, &isogeny.kernel()[1]).unwrap();
+
+        assert_eq!(generated.codomain(), isogeny.codomain());
+        for p in all_points(&curve) {
+            assert_eq!(generated.apply(&p), isogeny.apply(&p));
+        }
+    }
+
+    #[test]
+    fn with_kernel__should_reject_kernels_of_the_fermat_cubic() {
+        let curve = curve_7();
+
+        // d = 2 = -6β for β = 2, so ψ maps onto U³ + V³ + W³ = 0
+        let rejected: Vec<_> = curve
+            .three_torsion_points()
+            .skip(1)
+            .filter(|t| ThreeIsogeny::with_kernel(curve, t).is_none())
+            .collect();
+        assert_eq!(rejected.len(), 2);
+        // -1/β = 3
+        assert!(rejected.iter().all(|t| t.x().constant().value() == 3));
+    }
+
+    #[test]
+    #[should_panic(expected = "Kernel must be generated by a point of order 3")]
+    fn with_kernel__should_panic_when_point_is_not_of_order_3() {
+        let curve = curve_7();
+        let p = curve.kernel_point(Fq::new(1));
+
+        ThreeIsogeny::with_kernel(curve, &p);
+    }
+
+    #[test]
+    fn new__should_reject_unsuitable_curves() {
+        type F7 = Fq<7>;
+
+        // no cube root of unity in F5
+        assert!(ThreeIsogeny::new(paper_curve()).is_none());
+
+        // d = ε is not a unit
+        let a = RingElement::from_field(F7::new(1));
+        let d = RingElement::new(F7::new(0), F7::new(1));
+        assert!(ThreeIsogeny::new(TwistedHessianCurve::new(a, d)).is_none());
+    }
+}
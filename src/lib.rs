@@ -7,17 +7,76 @@
 #![deny(missing_docs)]
 #![deny(warnings)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+pub mod affine;
+pub mod counting;
 pub mod curve;
 pub mod dh;
+#[cfg(feature = "alloc")]
+pub mod edwards;
+#[cfg(feature = "alloc")]
+pub mod elligator;
+#[cfg(feature = "alloc")]
+pub mod encoding;
+pub mod extension;
 pub mod field;
+#[cfg(feature = "alloc")]
+pub mod fixed_base;
+pub mod glv;
+pub mod group;
+#[cfg(feature = "alloc")]
+pub mod hash_to_curve;
+pub mod isogeny;
+#[cfg(feature = "alloc")]
+pub mod msm;
+pub mod pairing;
+pub mod params;
+#[cfg(feature = "alloc")]
+pub mod poly;
 pub mod projective;
+#[cfg(feature = "rand")]
+pub mod random;
+pub mod reduction;
 pub mod ring;
+pub mod scalar;
+pub mod sha256;
+#[cfg(test)]
+mod test_support;
+pub mod torsion;
+pub mod weierstrass;
+pub mod wnaf;
 
 // convenient re-exports
+pub use affine::Affine;
 pub use curve::TwistedHessianCurve;
+pub use extension::Fq2;
 pub use field::Fq;
-pub use projective::Projective;
+#[cfg(feature = "alloc")]
+pub use fixed_base::FixedBaseTable;
+pub use group::{
+    CurveGroup,
+    CurvePoint,
+    Group,
+};
+pub use params::{
+    CurveParams,
+    Point,
+};
+#[cfg(feature = "alloc")]
+pub use poly::Polynomial;
+pub use projective::{
+    Projective,
+    ScalarMulMethod,
+};
 pub use ring::RingElement;
+pub use scalar::Scalar;
+pub use weierstrass::{
+    JacobianPoint,
+    WeierstrassCurve,
+    WeierstrassPoint,
+};
 
 #[cfg(test)]
 use crabtime as _;
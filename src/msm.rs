@@ -0,0 +1,179 @@
+//! Multi-scalar multiplication Σ kᵢ·Pᵢ
+
+use crate::{
+    projective::Projective,
+    ring::RingElement,
+    scalar::Scalar,
+    wnaf::Wnaf,
+};
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+/// Inputs up to this size use Straus interleaving, larger ones Pippenger's buckets
+pub const STRAUS_THRESHOLD: usize = 64;
+
+/// wNAF width used for the per-point tables in Straus' method
+const STRAUS_WIDTH: u32 = 4;
+
+/// Compute Σ kᵢ·Pᵢ with Straus–Shamir interleaving
+///
+/// All scalars share one chain of doublings; each point gets a table of odd
+/// multiples and contributes an addition per non-zero digit of its wNAF.
+pub fn straus<const Q: u64>(
+    points: &[Projective<Q>],
+    scalars: &[Scalar],
+    a: RingElement<Q>,
+) -> Projective<Q> {
+    assert_eq!(
+        points.len(),
+        scalars.len(),
+        "Points and scalars must have the same length"
+    );
+
+    let table_len = (1usize << STRAUS_WIDTH) >> 2;
+    let tables: Vec<Vec<Projective<Q>>> = points
+        .iter()
+        .map(|p| {
+            let double = p.double(a);
+            let mut table = Vec::with_capacity(table_len);
+            let mut multiple = *p;
+            for _ in 0..table_len {
+                table.push(multiple);
+                multiple = multiple.add(&double, a);
+            }
+            table
+        })
+        .collect();
+
+    let recoded: Vec<Wnaf> = scalars.iter().map(|k| Wnaf::new(k, STRAUS_WIDTH)).collect();
+    let len = recoded.iter().map(|w| w.digits().len()).max().unwrap_or(0);
+
+    let mut result = Projective::identity();
+    for i in (0..len).rev() {
+        result = result.double(a);
+
+        for (wnaf, table) in recoded.iter().zip(&tables) {
+            let digit = wnaf.digits().get(i).copied().unwrap_or(0);
+            let index = usize::from(digit.unsigned_abs() >> 1);
+            if digit > 0 {
+                result = result.add(&table[index], a);
+            } else if digit < 0 {
+                result = result.add(&table[index].negate(), a);
+            }
+        }
+    }
+
+    result
+}
+
+/// Compute Σ kᵢ·Pᵢ with Pippenger's bucket method
+///
+/// Each c-bit window sorts the points into 2^c - 1 buckets by digit and sums the
+/// buckets with a running sum, so the cost per window is about n + 2^(c+1) additions.
+pub fn pippenger<const Q: u64>(
+    points: &[Projective<Q>],
+    scalars: &[Scalar],
+    a: RingElement<Q>,
+) -> Projective<Q> {
+    assert_eq!(
+        points.len(),
+        scalars.len(),
+        "Points and scalars must have the same length"
+    );
+
+    // roughly log2(n) bits per window
+    let window = points.len().max(1).ilog2().clamp(1, 16);
+    // only as many windows as the widest scalar needs
+    let bits = scalars.iter().map(Scalar::bits).max().unwrap_or(0);
+    let windows = bits.div_ceil(window);
+
+    let mut result = Projective::identity();
+    for w in (0..windows).rev() {
+        for _ in 0..window {
+            result = result.double(a);
+        }
+
+        let shift = w.saturating_mul(window);
+        let mut buckets = vec![Projective::identity(); 1usize << window];
+        for (point, scalar) in points.iter().zip(scalars) {
+            let digit = scalar.bits_at(shift, window);
+            if digit != 0 {
+                let bucket = &mut buckets[usize::try_from(digit).expect("qed")];
+                *bucket = bucket.add(point, a);
+            }
+        }
+
+        // Σ j·Bⱼ as a sum of suffix sums
+        let mut running = Projective::identity();
+        let mut window_sum = Projective::identity();
+        for bucket in buckets.iter().skip(1).rev() {
+            running = running.add(bucket, a);
+            window_sum = window_sum.add(&running, a);
+        }
+
+        result = result.add(&window_sum, a);
+    }
+
+    result
+}
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::paper_curve_and_generator;
+    use rand::{
+        Rng,
+        SeedableRng,
+        rngs::StdRng,
+    };
+
+    fn paper_inputs(n: usize) -> (Vec<Projective<5>>, Vec<Scalar>, RingElement<5>) {
+        let (curve, generator) = paper_curve_and_generator();
+        let a = curve.a();
+
+        let mut rng = StdRng::seed_from_u64(n as u64);
+        let points = (0..n)
+            .map(|_| generator.scalar_mul(rng.gen_range(0..45u64), a))
+            .collect();
+        let scalars = (0..n).map(|_| Scalar::from_limbs(rng.r#gen())).collect();
+
+        (points, scalars, a)
+    }
+
+    fn naive(
+        points: &[Projective<5>],
+        scalars: &[Scalar],
+        a: RingElement<5>,
+    ) -> Projective<5> {
+        points
+            .iter()
+            .zip(scalars)
+            .fold(Projective::identity(), |acc, (p, k)| {
+                acc.add(&p.scalar_mul(*k, a), a)
+            })
+    }
+
+    #[test]
+    fn straus_and_pippenger__match_sum_of_scalar_muls() {
+        for n in [0, 1, 2, 7, 33, 150] {
+            let (points, scalars, a) = paper_inputs(n);
+            let expected = naive(&points, &scalars, a);
+
+            assert!(straus(&points, &scalars, a).is_equal(&expected));
+            assert!(pippenger(&points, &scalars, a).is_equal(&expected));
+            assert!(
+                Projective::multi_scalar_mul(&points, &scalars, a).is_equal(&expected)
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Points and scalars must have the same length")]
+    fn multi_scalar_mul__should_panic_on_length_mismatch() {
+        let (points, _, a) = paper_inputs(3);
+        Projective::multi_scalar_mul(&points, &[Scalar::ONE, Scalar::ONE], a);
+    }
+}
@@ -0,0 +1,619 @@
+//! Tate and Weil pairings on twisted Hessian curves over Fq
+//!
+//! Miller's algorithm works directly with lines in the plane of the curve. The
+//! identity O = [0:-1:1] is an inflection point with tangent T_O = dX + 3Y + 3Z, so
+//! the line l through A and B (the tangent when A = B) meets the curve again in
+//! -(A + B), and the line v through A + B and O meets it again in -(A + B). The
+//! function l/v has divisor (A) + (B) - (A + B) - (O); when A + B = O, v is T_O.
+//!
+//! Each l/v is normalised to have leading coefficient 1 at O in the uniformiser X/Z,
+//! along which Y/Z = -1 - (d/3)X/Z + O((X/Z)³). Then f_{r,P} can be evaluated at a
+//! point Q instead of a divisor (Q) - (O), which is what makes the embedding degree 1
+//! Tate pairing and the Weil pairing e(P, Q) = (-1)^r f_{r,P}(Q)/f_{r,Q}(P) work.
+//!
+//! Values live in [`Fq2`], which covers embedding degrees 1 and 2. Only curves over
+//! Fq, i.e. with zero ε-parts, are supported.
+
+use crate::{
+    counting::prime_factors,
+    curve::TwistedHessianCurve,
+    extension::Fq2,
+    field::Fq,
+    projective::Projective,
+};
+use core::ops::{
+    Add,
+    Mul,
+    Sub,
+};
+
+/// A point [X:Y:Z] with coordinates in Fq² on a twisted Hessian curve over Fq
+#[derive(Debug, Clone, Copy)]
+pub struct ExtensionPoint<const Q: u64> {
+    x: Fq2<Q>,
+    y: Fq2<Q>,
+    z: Fq2<Q>,
+}
+
+impl<const Q: u64> ExtensionPoint<Q> {
+    /// Create a new point [X:Y:Z]
+    pub const fn new(x: Fq2<Q>, y: Fq2<Q>, z: Fq2<Q>) -> Self {
+        ExtensionPoint { x, y, z }
+    }
+
+    /// Embed a point with coordinates in Fq
+    pub fn from_projective(point: &Projective<Q>) -> Self {
+        let coordinates = [point.x(), point.y(), point.z()];
+        assert!(
+            coordinates.iter().all(|c| c.epsilon_coeff().value() == 0),
+            "Point must have coordinates in Fq"
+        );
+
+        let [x, y, z] = coordinates.map(|c| Fq2::from_base(c.constant()));
+        ExtensionPoint::new(x, y, z)
+    }
+
+    /// Create the identity element [0:-1:1]
+    pub fn identity() -> Self {
+        let zero = Fq2::from_base(Fq::new(0));
+        let one = Fq2::from_base(Fq::new(1));
+
+        ExtensionPoint::new(zero, zero.sub(one), one)
+    }
+
+    /// Get the x-coordinate
+    pub fn x(&self) -> Fq2<Q> {
+        self.x
+    }
+
+    /// Get the y-coordinate
+    pub fn y(&self) -> Fq2<Q> {
+        self.y
+    }
+
+    /// Get the z-coordinate
+    pub fn z(&self) -> Fq2<Q> {
+        self.z
+    }
+
+    /// Check if this is the identity point
+    pub fn is_identity(&self) -> bool {
+        self.x.is_zero() && self.y.add(self.z).is_zero()
+    }
+
+    /// Check if the point lies on aX³ + Y³ + Z³ = dXYZ
+    pub fn is_on_curve(&self, a: Fq2<Q>, d: Fq2<Q>) -> bool {
+        let (x, y, z) = (self.x, self.y, self.z);
+
+        a.mul(x)
+            .mul(x)
+            .mul(x)
+            .add(y.mul(y).mul(y))
+            .add(z.mul(z).mul(z))
+            == d.mul(x).mul(y).mul(z)
+    }
+
+    /// Apply the Frobenius map to every coordinate
+    pub fn frobenius(&self) -> Self {
+        ExtensionPoint::new(self.x.frobenius(), self.y.frobenius(), self.z.frobenius())
+    }
+
+    /// Check if the point is fixed by the Frobenius, i.e. has coordinates in Fq up to
+    /// scaling
+    pub fn is_rational(&self) -> bool {
+        *self == self.frobenius()
+    }
+
+    /// Negate a point, -[X:Y:Z] = [X:Z:Y]
+    pub fn negate(&self) -> Self {
+        ExtensionPoint::new(self.x, self.z, self.y)
+    }
+
+    /// Add two points with the complete pair of addition laws, see
+    /// [`Projective::add`]
+    pub fn add(&self, other: &Self, a: Fq2<Q>) -> Self {
+        let (x1, y1, z1) = (self.x, self.y, self.z);
+        let (x2, y2, z2) = (other.x, other.y, other.z);
+
+        // X₃ = X₁²Y₂Z₂ - X₂²Y₁Z₁, Y₃ = Z₁²X₂Y₂ - Z₂²X₁Y₁, Z₃ = Y₁²X₂Z₂ - Y₂²X₁Z₁
+        let standard = ExtensionPoint::new(
+            x1.mul(x1).mul(y2).mul(z2).sub(x2.mul(x2).mul(y1).mul(z1)),
+            z1.mul(z1).mul(x2).mul(y2).sub(z2.mul(z2).mul(x1).mul(y1)),
+            y1.mul(y1).mul(x2).mul(z2).sub(y2.mul(y2).mul(x1).mul(z1)),
+        );
+        if !standard.is_zero() {
+            return standard;
+        }
+
+        // X₃ = Z₂²X₁Z₁ - Y₁²X₂Y₂, Y₃ = Y₂²Y₁Z₁ - aX₁²X₂Z₂, Z₃ = aX₂²X₁Y₁ - Z₁²Y₂Z₂
+        ExtensionPoint::new(
+            z2.mul(z2).mul(x1).mul(z1).sub(y1.mul(y1).mul(x2).mul(y2)),
+            y2.mul(y2)
+                .mul(y1)
+                .mul(z1)
+                .sub(a.mul(x1).mul(x1).mul(x2).mul(z2)),
+            a.mul(x2)
+                .mul(x2)
+                .mul(x1)
+                .mul(y1)
+                .sub(z1.mul(z1).mul(y2).mul(z2)),
+        )
+    }
+
+    /// Double a point, see [`Projective::double`]
+    pub fn double(&self, a: Fq2<Q>) -> Self {
+        let ax_cubed = a.mul(self.x).mul(self.x).mul(self.x);
+        let y_cubed = self.y.mul(self.y).mul(self.y);
+        let z_cubed = self.z.mul(self.z).mul(self.z);
+
+        ExtensionPoint::new(
+            self.x.mul(z_cubed.sub(y_cubed)),
+            self.z.mul(y_cubed.sub(ax_cubed)),
+            self.y.mul(ax_cubed.sub(z_cubed)),
+        )
+    }
+
+    /// Multiply a point by a scalar using double-and-add
+    pub fn scalar_mul(&self, scalar: u64, a: Fq2<Q>) -> Self {
+        let mut result = ExtensionPoint::identity();
+
+        for bit in (0..u64::BITS).rev() {
+            result = result.double(a);
+            if (scalar >> bit) & 1 == 1 {
+                result = result.add(self, a);
+            }
+        }
+
+        result
+    }
+
+    fn is_zero(&self) -> bool {
+        self.x.is_zero() && self.y.is_zero() && self.z.is_zero()
+    }
+}
+
+impl<const Q: u64> PartialEq for ExtensionPoint<Q> {
+    fn eq(&self, other: &Self) -> bool {
+        cross(self, other).iter().all(Fq2::is_zero)
+    }
+}
+
+impl<const Q: u64> Eq for ExtensionPoint<Q> {}
+
+/// Pairings of r-torsion points on a twisted Hessian curve over Fq, for a prime r
+/// with embedding degree 1 or 2
+#[derive(Debug, Clone, Copy)]
+pub struct Pairing<const Q: u64> {
+    a: Fq2<Q>,
+    d: Fq2<Q>,
+    order: u64,
+    embedding_degree: u32,
+    final_exponent: u64,
+}
+
+impl<const Q: u64> Pairing<Q> {
+    /// Set up the pairings of `order`-torsion points, or `None` unless the curve is
+    /// over Fq, i.e. a and d have no ε-parts, and `order` is an odd prime dividing
+    /// q - 1 or q + 1
+    pub fn new(curve: &TwistedHessianCurve<Q>, order: u64) -> Option<Self> {
+        let over_fq = curve.a().epsilon_coeff().value() == 0
+            && curve.d().epsilon_coeff().value() == 0;
+        if !over_fq || order < 3 || !is_prime(order) {
+            return None;
+        }
+
+        let q_minus_one = Q.checked_sub(1).expect("Q must be gt 1");
+        let embedding_degree = if q_minus_one.checked_rem(order) == Some(0) {
+            1
+        } else if Q.checked_add(1)?.checked_rem(order) == Some(0) {
+            2
+        } else {
+            return None;
+        };
+
+        // (q^k - 1)/r
+        let field_order = Q.checked_pow(embedding_degree)?;
+        let final_exponent = field_order
+            .checked_sub(1)?
+            .checked_div(order)
+            .expect("order is non-zero");
+
+        Some(Pairing {
+            a: Fq2::from_base(curve.a().constant()),
+            d: Fq2::from_base(curve.d().constant()),
+            order,
+            embedding_degree,
+            final_exponent,
+        })
+    }
+
+    /// Get the curve parameter a in Fq²
+    pub fn a(&self) -> Fq2<Q> {
+        self.a
+    }
+
+    /// Get the curve parameter d in Fq²
+    pub fn d(&self) -> Fq2<Q> {
+        self.d
+    }
+
+    /// Get the prime order r of the paired points
+    pub fn order(&self) -> u64 {
+        self.order
+    }
+
+    /// Get the embedding degree k, the smallest with r | q^k - 1
+    pub fn embedding_degree(&self) -> u32 {
+        self.embedding_degree
+    }
+
+    /// Check if a point lies on the curve over Fq²
+    pub fn contains(&self, point: &ExtensionPoint<Q>) -> bool {
+        !point.is_zero() && point.is_on_curve(self.a, self.d)
+    }
+
+    /// Check if a point on the curve is killed by r
+    pub fn is_torsion(&self, point: &ExtensionPoint<Q>) -> bool {
+        self.contains(point) && point.scalar_mul(self.order, self.a).is_identity()
+    }
+
+    /// Evaluate the normalised Miller function f_{r,P}, with divisor r(P) - r(O), at Q
+    ///
+    /// Gives `None` when one of the lines vanishes at Q, which only happens if Q is a
+    /// multiple of P.
+    pub fn miller_loop(
+        &self,
+        p: &ExtensionPoint<Q>,
+        q: &ExtensionPoint<Q>,
+    ) -> Option<Fq2<Q>> {
+        let one = Fq2::from_base(Fq::new(1));
+        let top = u64::BITS.saturating_sub(self.order.leading_zeros());
+
+        let mut t = *p;
+        let (mut numerator, mut denominator) = (one, one);
+
+        for bit in (0..top.saturating_sub(1)).rev() {
+            let (n, d) = self.line(&t, &t, q)?;
+            numerator = numerator.mul(numerator).mul(n);
+            denominator = denominator.mul(denominator).mul(d);
+            t = t.double(self.a);
+
+            if (self.order >> bit) & 1 == 1 {
+                let (n, d) = self.line(&t, p, q)?;
+                numerator = numerator.mul(n);
+                denominator = denominator.mul(d);
+                t = t.add(p, self.a);
+            }
+        }
+
+        Some(numerator.mul(denominator.inv()))
+    }
+
+    /// Reduced Tate pairing f_{r,P}(Q)^((q^k - 1)/r) of P ∈ E(Fq)[r] and Q ∈ E(Fq^k)
+    pub fn tate(&self, p: &ExtensionPoint<Q>, q: &ExtensionPoint<Q>) -> Fq2<Q> {
+        assert!(
+            self.is_torsion(p) && p.is_rational(),
+            "First point must be an r-torsion point over Fq"
+        );
+        assert!(self.contains(q), "Point must be on the curve");
+
+        let one = Fq2::from_base(Fq::new(1));
+        if p.is_identity() {
+            return one;
+        }
+
+        match self.miller_loop(p, q) {
+            Some(value) => value.pow(self.final_exponent),
+            // Q ∈ ⟨P⟩ ∪ {O} lies over Fq, where every value is killed by the exponent
+            None => {
+                assert!(
+                    self.embedding_degree == 2,
+                    "For embedding degree 1 the second point must not be a multiple of \
+                     the first"
+                );
+                one
+            }
+        }
+    }
+
+    /// Weil pairing (-1)^r f_{r,P}(Q)/f_{r,Q}(P) of P, Q ∈ E[r]
+    pub fn weil(&self, p: &ExtensionPoint<Q>, q: &ExtensionPoint<Q>) -> Fq2<Q> {
+        assert!(
+            self.is_torsion(p) && self.is_torsion(q),
+            "Points must be r-torsion points"
+        );
+
+        let one = Fq2::from_base(Fq::new(1));
+        if p.is_identity() || q.is_identity() || p == q {
+            return one;
+        }
+
+        // a vanishing line means that one point is a multiple of the other
+        match (self.miller_loop(p, q), self.miller_loop(q, p)) {
+            (Some(f_p), Some(f_q)) => {
+                // r is odd
+                Fq2::from_base(Fq::new(0)).sub(f_p.mul(f_q.inv()))
+            }
+            _ => one,
+        }
+    }
+
+    /// Evaluate at Q the normalised function l/v of divisor (A) + (B) - (A+B) - (O), as
+    /// a numerator and a denominator
+    fn line(
+        &self,
+        a: &ExtensionPoint<Q>,
+        b: &ExtensionPoint<Q>,
+        q: &ExtensionPoint<Q>,
+    ) -> Option<(Fq2<Q>, Fq2<Q>)> {
+        let (curve_a, d) = (self.a, self.d);
+        let three = Fq2::from_base(Fq::new(3));
+        let sum = a.add(b, curve_a);
+
+        let line = if a == b {
+            // the tangent, i.e. the gradient of aX³ + Y³ + Z³ - dXYZ
+            let (x, y, z) = (a.x, a.y, a.z);
+            [
+                three.mul(curve_a).mul(x).mul(x).sub(d.mul(y).mul(z)),
+                three.mul(y).mul(y).sub(d.mul(x).mul(z)),
+                three.mul(z).mul(z).sub(d.mul(x).mul(y)),
+            ]
+        } else {
+            cross(a, b)
+        };
+        let line_at_q = evaluate(&line, q);
+        if line_at_q.is_zero() {
+            return None;
+        }
+
+        let identity = ExtensionPoint::identity();
+        if sum.is_identity() {
+            // l/T_O ~ (3l_x - dl_y)/3 · 27/(d³ - 27a) · (X/Z)⁻²
+            let tangent = evaluate(&[d, three, three], q);
+            if tangent.is_zero() {
+                return None;
+            }
+            let twenty_seven_a = Fq2::from_base(Fq::new(27)).mul(curve_a);
+            let nine = Fq2::from_base(Fq::new(9));
+
+            Some((
+                line_at_q.mul(d.mul(d).mul(d).sub(twenty_seven_a)),
+                tangent
+                    .mul(nine)
+                    .mul(three.mul(line[0]).sub(d.mul(line[1]))),
+            ))
+        } else {
+            // l/v ~ 3l(O)/(3v_x - dv_y) · (X/Z)⁻¹
+            let vertical = cross(&sum, &identity);
+            let vertical_at_q = evaluate(&vertical, q);
+            if vertical_at_q.is_zero() {
+                return None;
+            }
+
+            Some((
+                line_at_q.mul(three.mul(vertical[0]).sub(d.mul(vertical[1]))),
+                vertical_at_q.mul(three).mul(evaluate(&line, &identity)),
+            ))
+        }
+    }
+}
+
+/// Coefficients of the line through two points, also zero exactly when they are equal
+fn cross<const Q: u64>(p: &ExtensionPoint<Q>, q: &ExtensionPoint<Q>) -> [Fq2<Q>; 3] {
+    [
+        p.y.mul(q.z).sub(p.z.mul(q.y)),
+        p.z.mul(q.x).sub(p.x.mul(q.z)),
+        p.x.mul(q.y).sub(p.y.mul(q.x)),
+    ]
+}
+
+/// Evaluate the linear form with the given coefficients at a point
+fn evaluate<const Q: u64>(line: &[Fq2<Q>; 3], point: &ExtensionPoint<Q>) -> Fq2<Q> {
+    line[0]
+        .mul(point.x)
+        .add(line[1].mul(point.y))
+        .add(line[2].mul(point.z))
+}
+
+/// Primality by trial division
+fn is_prime(n: u64) -> bool {
+    n >= 2 && prime_factors(n).next() == Some(n)
+}
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ring::RingElement;
+
+    /// Every point of the curve aX³ + Y³ + Z³ = dXYZ over Fq
+    fn rational_points<const Q: u64>(a: u64, d: u64) -> Vec<ExtensionPoint<Q>> {
+        let (a, d) = (Fq2::from_base(Fq::new(a)), Fq2::from_base(Fq::new(d)));
+        let field = |v| Fq2::from_base(Fq::<Q>::new(v));
+
+        let finite = (0..Q).flat_map(|x| {
+            (0..Q).map(move |y| ExtensionPoint::new(field(x), field(y), field(1)))
+        });
+        let at_infinity =
+            (0..Q).map(|x| ExtensionPoint::new(field(x), field(1), field(0)));
+
+        finite
+            .chain(at_infinity)
+            .filter(|p| p.is_on_curve(a, d))
+            .collect()
+    }
+
+    /// A primitive cube root of unity in Fq²
+    fn omega<const Q: u64>() -> Fq2<Q> {
+        let one = Fq2::from_base(Fq::new(1));
+
+        (0..Q)
+            .flat_map(|c0| (0..Q).map(move |c1| Fq2::new(Fq::new(c0), Fq::new(c1))))
+            .find(|w| *w != one && w.pow(3) == one)
+            .expect("q ≡ 2 mod 3, so ω lies in Fq² \\ Fq")
+    }
+
+    /// On X³ + Y³ + Z³ = 0 over F41, which has 42 points, P of order 7 and its image
+    /// under the distortion map [X:Y:Z] ↦ [ωX:Y:Z]
+    fn supersingular_41() -> (Pairing<41>, ExtensionPoint<41>, ExtensionPoint<41>) {
+        let one = RingElement::from_field(Fq::new(1));
+        let curve = TwistedHessianCurve::new(one, RingElement::from_field(Fq::new(0)));
+        let pairing = Pairing::new(&curve, 7).unwrap();
+
+        let points = rational_points::<41>(1, 0);
+        assert_eq!(points.len(), 42);
+        let p = points
+            .iter()
+            .map(|p| p.scalar_mul(6, pairing.a()))
+            .find(|p| !p.is_identity())
+            .unwrap();
+        let q = ExtensionPoint::new(omega().mul(p.x()), p.y(), p.z());
+
+        (pairing, p, q)
+    }
+
+    #[test]
+    fn new__computes_the_embedding_degree() {
+        let one = RingElement::from_field(Fq::<41>::new(1));
+        let curve = TwistedHessianCurve::new(one, RingElement::from_field(Fq::new(0)));
+
+        assert_eq!(Pairing::new(&curve, 7).unwrap().embedding_degree(), 2);
+        assert_eq!(Pairing::new(&curve, 5).unwrap().embedding_degree(), 1);
+        assert!(Pairing::new(&curve, 2).is_none());
+        assert!(Pairing::new(&curve, 21).is_none());
+        assert!(Pairing::new(&curve, 11).is_none());
+    }
+
+    #[test]
+    fn new__rejects_curves_with_epsilon_parts() {
+        type F41 = Fq<41>;
+
+        let a = RingElement::new(F41::new(1), F41::new(1)); // 1+ε
+        let curve = TwistedHessianCurve::new(a, RingElement::from_field(F41::new(0)));
+
+        assert!(Pairing::new(&curve, 7).is_none());
+        assert!(Pairing::new(&curve.reduce(), 7).is_some());
+    }
+
+    #[test]
+    fn is_prime__kats() {
+        let primes: Vec<_> = (0..30).filter(|&n| is_prime(n)).collect();
+
+        assert_eq!(primes, [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert!(is_prime(2_147_483_647));
+        assert!(!is_prime(2_147_483_649));
+    }
+
+    #[test]
+    fn tate__is_bilinear_and_non_degenerate_for_embedding_degree_2() {
+        let (pairing, p, q) = supersingular_41();
+        let a = pairing.a();
+        let one = Fq2::from_base(Fq::new(1));
+
+        assert!(pairing.is_torsion(&q) && !q.is_rational());
+        let base = pairing.tate(&p, &q);
+        assert_ne!(base, one);
+        assert_eq!(base.pow(7), one);
+
+        for i in 1..7u64 {
+            for j in 1..7u64 {
+                let value = pairing.tate(&p.scalar_mul(i, a), &q.scalar_mul(j, a));
+                assert_eq!(value, base.pow(i.saturating_mul(j)));
+            }
+        }
+
+        // points over Fq pair trivially
+        assert_eq!(pairing.tate(&p, &p.scalar_mul(3, a)), one);
+    }
+
+    #[test]
+    fn weil__is_bilinear_alternating_and_non_degenerate() {
+        let (pairing, p, q) = supersingular_41();
+        let a = pairing.a();
+        let one = Fq2::from_base(Fq::new(1));
+
+        let base = pairing.weil(&p, &q);
+        assert_ne!(base, one);
+        assert_eq!(base.pow(7), one);
+        assert_eq!(pairing.weil(&q, &p), base.inv());
+        assert_eq!(pairing.weil(&p, &p.scalar_mul(3, a)), one);
+
+        for i in 1..7u64 {
+            for j in 1..7u64 {
+                let r = p.scalar_mul(i, a);
+                let s = q.scalar_mul(j, a);
+                assert_eq!(pairing.weil(&r, &s), base.pow(i.saturating_mul(j)));
+                assert_eq!(pairing.weil(&r.add(&s, a), &r.add(&s, a)), one);
+            }
+        }
+    }
+
+    #[test]
+    fn pairings__work_with_embedding_degree_1_on_the_3_torsion() {
+        type F7 = Fq<7>;
+
+        // all nine points of order dividing 3 lie over F7
+        let curve = TwistedHessianCurve::new(
+            RingElement::from_field(F7::new(1)),
+            RingElement::from_field(F7::new(2)),
+        );
+        let pairing = Pairing::new(&curve, 3).unwrap();
+        assert_eq!(pairing.embedding_degree(), 1);
+
+        let torsion: Vec<_> = curve
+            .three_torsion_points()
+            .map(|t| ExtensionPoint::from_projective(&t))
+            .collect();
+        assert_eq!(torsion.len(), 9);
+
+        let (a, one) = (pairing.a(), Fq2::from_base(Fq::new(1)));
+        let (p, q) = (torsion[1], torsion[3]);
+        let base = pairing.weil(&p, &q);
+        assert_ne!(base, one);
+        assert!(base.is_in_base_field() && base.pow(3) == one);
+
+        for r in &torsion {
+            for s in &torsion {
+                let weil = pairing.weil(r, s);
+                assert!(weil.pow(3) == one);
+                assert_eq!(pairing.weil(&r.add(s, a), s), weil.mul(pairing.weil(s, s)));
+                assert_eq!(
+                    pairing.weil(r, &s.add(&q, a)),
+                    weil.mul(pairing.weil(r, &q))
+                );
+            }
+        }
+
+        // t(P, ·) is a non-trivial character, checked away from the multiples of P
+        let outside = &torsion[3..];
+        assert!(outside.iter().any(|s| pairing.tate(&p, s) != one));
+        for s in outside {
+            let tate = pairing.tate(&p, s);
+            assert_eq!(tate.pow(3), one);
+            assert_eq!(pairing.tate(&p.add(&p, a), s), tate.mul(tate));
+
+            for s2 in outside {
+                let sum = s.add(s2, a);
+                if outside.contains(&sum) {
+                    assert_eq!(pairing.tate(&p, &sum), tate.mul(pairing.tate(&p, s2)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Points must be r-torsion points")]
+    fn weil__should_panic_when_point_is_not_torsion() {
+        let (pairing, p, _) = supersingular_41();
+        let points = rational_points::<41>(1, 0);
+        let generator = points
+            .iter()
+            .find(|g| {
+                !g.scalar_mul(6, pairing.a()).is_identity()
+                    && !g.scalar_mul(7, pairing.a()).is_identity()
+            })
+            .unwrap();
+
+        pairing.weil(&p, generator);
+    }
+}
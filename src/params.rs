@@ -0,0 +1,285 @@
+//! Curves fixed at the type level
+//!
+//! A [`CurveParams`] implementation names one curve, and [`Point`] carries it as a type
+//! parameter, so adding points of two different curves does not compile:
+//!
+//! ```compile_fail
+//! use hessian_rs::{Fq, RingElement, params::{CurveParams, Point}, group::Group};
+//!
+//! struct Curve1;
+//! impl CurveParams<5> for Curve1 {
+//!     const A: RingElement<5> = RingElement::new(Fq::new(1), Fq::new(1));
+//!     const D: RingElement<5> = RingElement::new(Fq::new(1), Fq::new(1));
+//! }
+//!
+//! struct Curve2;
+//! impl CurveParams<5> for Curve2 {
+//!     const A: RingElement<5> = RingElement::new(Fq::new(1), Fq::new(1));
+//!     const D: RingElement<5> = RingElement::new(Fq::new(1), Fq::new(2));
+//! }
+//!
+//! let _ = Point::<Curve1, 5>::identity() + Point::<Curve2, 5>::identity();
+//! ```
+
+use crate::{
+    curve::TwistedHessianCurve,
+    group::{
+        CurveGroup,
+        Group,
+    },
+    projective::Projective,
+    ring::RingElement,
+    scalar::Scalar,
+};
+use core::{
+    fmt,
+    hash::{
+        Hash,
+        Hasher,
+    },
+    iter::Sum,
+    marker::PhantomData,
+    ops::{
+        Add,
+        AddAssign,
+        Mul,
+        Neg,
+        Sub,
+    },
+};
+
+/// Parameters of a twisted Hessian curve aX³ + Y³ + Z³ = dXYZ over Fq[ε]
+pub trait CurveParams<const Q: u64> {
+    /// The a parameter
+    const A: RingElement<Q>;
+
+    /// The d parameter
+    const D: RingElement<Q>;
+
+    /// A distinguished generator, if the curve has one
+    const GENERATOR: Option<Projective<Q>> = None;
+
+    /// Order of the generator
+    const ORDER: Option<Scalar> = None;
+
+    /// Index of the generated subgroup in the whole group of points
+    const COFACTOR: Option<Scalar> = None;
+
+    /// Get the curve as a value, checking that the parameters are valid
+    fn curve() -> TwistedHessianCurve<Q> {
+        TwistedHessianCurve::new(Self::A, Self::D)
+    }
+}
+
+/// A point on the curve `C`
+pub struct Point<C: CurveParams<Q>, const Q: u64> {
+    point: Projective<Q>,
+    curve: PhantomData<C>,
+}
+
+impl<C: CurveParams<Q>, const Q: u64> Point<C, Q> {
+    /// Wrap a point on the curve `C`
+    pub fn new(point: Projective<Q>) -> Self {
+        assert!(C::curve().contains(&point), "Point must be on the curve");
+
+        Point::from_projective(point)
+    }
+
+    /// Get the generator of `C`, if it has one
+    pub fn generator() -> Option<Self> {
+        C::GENERATOR.map(Point::new)
+    }
+
+    /// Get the underlying projective point
+    pub fn point(&self) -> Projective<Q> {
+        self.point
+    }
+
+    fn from_projective(point: Projective<Q>) -> Self {
+        Point {
+            point,
+            curve: PhantomData,
+        }
+    }
+}
+
+// manual impls, since derives would require `C` itself to be Clone, Debug, ...
+impl<C: CurveParams<Q>, const Q: u64> Clone for Point<C, Q> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C: CurveParams<Q>, const Q: u64> Copy for Point<C, Q> {}
+
+impl<C: CurveParams<Q>, const Q: u64> fmt::Debug for Point<C, Q> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Point").field(&self.point).finish()
+    }
+}
+
+impl<C: CurveParams<Q>, const Q: u64> PartialEq for Point<C, Q> {
+    fn eq(&self, other: &Self) -> bool {
+        self.point == other.point
+    }
+}
+
+impl<C: CurveParams<Q>, const Q: u64> Eq for Point<C, Q> {}
+
+impl<C: CurveParams<Q>, const Q: u64> Hash for Point<C, Q> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.point.hash(state);
+    }
+}
+
+impl<C: CurveParams<Q>, const Q: u64> Group for Point<C, Q> {
+    fn identity() -> Self {
+        Point::from_projective(Projective::identity())
+    }
+
+    fn is_identity(&self) -> bool {
+        self.point.is_identity()
+    }
+
+    fn double(&self) -> Self {
+        Point::from_projective(self.point.double(C::A))
+    }
+}
+
+impl<C: CurveParams<Q>, const Q: u64> CurveGroup<Q> for Point<C, Q> {
+    fn to_projective(&self) -> Projective<Q> {
+        self.point
+    }
+}
+
+impl<C: CurveParams<Q>, const Q: u64> Add for Point<C, Q> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Point::from_projective(self.point.add(&rhs.point, C::A))
+    }
+}
+
+#[allow(clippy::arithmetic_side_effects)]
+impl<C: CurveParams<Q>, const Q: u64> AddAssign for Point<C, Q> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<C: CurveParams<Q>, const Q: u64> Neg for Point<C, Q> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Point::from_projective(self.point.negate())
+    }
+}
+
+#[allow(clippy::arithmetic_side_effects)]
+impl<C: CurveParams<Q>, const Q: u64> Sub for Point<C, Q> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl<C: CurveParams<Q>, const Q: u64> Mul<Scalar> for Point<C, Q> {
+    type Output = Self;
+
+    /// Variable-time double-and-add, see [`Projective::scalar_mul`]
+    fn mul(self, rhs: Scalar) -> Self {
+        Point::from_projective(self.point.scalar_mul(rhs, C::A))
+    }
+}
+
+#[allow(clippy::arithmetic_side_effects)]
+impl<C: CurveParams<Q>, const Q: u64> Sum for Point<C, Q> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Point::identity(), |acc, p| acc + p)
+    }
+}
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::Fq;
+
+    /// The curve of Section 3.1 of the paper, a = d = 1+ε over F5[ε]
+    struct PaperCurve;
+
+    impl CurveParams<5> for PaperCurve {
+        const A: RingElement<5> = RingElement::new(Fq::new(1), Fq::new(1));
+        const D: RingElement<5> = RingElement::new(Fq::new(1), Fq::new(1));
+        const GENERATOR: Option<Projective<5>> = Some(Projective::new(
+            RingElement::from_field(Fq::new(1)),
+            RingElement::from_field(Fq::new(2)),
+            RingElement::new(Fq::new(3), Fq::new(1)),
+        ));
+        const ORDER: Option<Scalar> = Some(Scalar::from_u64(45));
+        const COFACTOR: Option<Scalar> = Some(Scalar::ONE);
+    }
+
+    type PaperPoint = Point<PaperCurve, 5>;
+
+    /// Diffie-Hellman written against the trait only
+    #[allow(clippy::arithmetic_side_effects)]
+    fn shared_secret<G: Group>(g: G, alice: Scalar, bob: Scalar) -> (G, G) {
+        (g * bob * alice, g * alice * bob)
+    }
+
+    #[test]
+    #[allow(clippy::arithmetic_side_effects)]
+    fn point__kats_paper_3_1() {
+        let g = PaperPoint::generator().unwrap();
+
+        // 4P = [1, 4, 3+2ε]
+        let four_p = PaperPoint::new(Projective::new(
+            RingElement::from_field(Fq::new(1)),
+            RingElement::from_field(Fq::new(4)),
+            RingElement::new(Fq::new(3), Fq::new(2)),
+        ));
+        assert_eq!(g * Scalar::from(4u64), four_p);
+        assert_eq!(g.double().double(), four_p);
+        assert_eq!(four_p + g - g, four_p);
+
+        let order = PaperCurve::ORDER.unwrap();
+        assert!((g * order).is_identity());
+        assert_eq!(
+            (1..=9u64).map(|k| g.scalar_mul(k)).sum::<PaperPoint>(),
+            PaperPoint::identity()
+        );
+    }
+
+    #[test]
+    fn point__matches_curve_point_arithmetic() {
+        let g = PaperPoint::generator().unwrap();
+        let dynamic = crate::group::CurvePoint::new(PaperCurve::curve(), g.point());
+
+        for k in 0..45u64 {
+            assert_eq!(g.scalar_mul(k).point(), dynamic.scalar_mul(k).point());
+        }
+    }
+
+    #[test]
+    fn group__supports_generic_protocol_code() {
+        let g = PaperPoint::generator().unwrap();
+
+        // the shared secret from Section 3.1.2 is 5P = [1, 3+2ε, 4+3ε]
+        let (alice, bob) = shared_secret(g, 4u64.into(), 35u64.into());
+        assert_eq!(alice, bob);
+        assert_eq!(alice, g.scalar_mul(5u64));
+        assert_eq!(alice.to_affine(), alice.point().to_affine());
+    }
+
+    #[test]
+    #[should_panic(expected = "Point must be on the curve")]
+    fn new__should_panic_when_point_is_off_curve() {
+        PaperPoint::new(Projective::new(
+            RingElement::from_field(Fq::new(1)),
+            RingElement::from_field(Fq::new(1)),
+            RingElement::from_field(Fq::new(1)),
+        ));
+    }
+}
@@ -0,0 +1,400 @@
+//! Univariate polynomials over Fq
+
+use crate::field::Fq;
+use alloc::{
+    vec,
+    vec::Vec,
+};
+use core::ops::{
+    Add,
+    Mul,
+    Sub,
+};
+
+/// Polynomial over Fq, stored with the constant coefficient first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial<const Q: u64> {
+    // invariant: no trailing zero coefficients, so the zero polynomial is empty
+    coeffs: Vec<Fq<Q>>,
+}
+
+impl<const Q: u64> Polynomial<Q> {
+    /// Create a polynomial from its coefficients, constant coefficient first
+    pub fn new(coeffs: Vec<Fq<Q>>) -> Self {
+        let mut poly = Polynomial { coeffs };
+        poly.normalize();
+        poly
+    }
+
+    /// The zero polynomial
+    pub fn zero() -> Self {
+        Polynomial { coeffs: Vec::new() }
+    }
+
+    /// The constant polynomial c
+    pub fn constant(c: Fq<Q>) -> Self {
+        Polynomial::new(vec![c])
+    }
+
+    /// The polynomial c·xⁿ
+    pub fn monomial(c: Fq<Q>, degree: usize) -> Self {
+        let mut coeffs = vec![Fq::new(0); degree];
+        coeffs.push(c);
+        Polynomial::new(coeffs)
+    }
+
+    /// The polynomial x
+    pub fn x() -> Self {
+        Polynomial::monomial(Fq::new(1), 1)
+    }
+
+    /// Get the coefficients, constant coefficient first
+    pub fn coeffs(&self) -> &[Fq<Q>] {
+        &self.coeffs
+    }
+
+    /// Get the degree of the polynomial, `None` for the zero polynomial
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    /// Check if this is the zero polynomial
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// Get the leading coefficient, `None` for the zero polynomial
+    pub fn leading_coeff(&self) -> Option<Fq<Q>> {
+        self.coeffs.last().copied()
+    }
+
+    /// Evaluate the polynomial at a point using Horner's rule
+    pub fn evaluate(&self, x: Fq<Q>) -> Fq<Q> {
+        self.coeffs
+            .iter()
+            .rev()
+            .fold(Fq::new(0), |acc, &c| acc.mul(x).add(c))
+    }
+
+    /// Formal derivative
+    pub fn derivative(&self) -> Self {
+        let coeffs = self
+            .coeffs
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, &c)| c.mul(Fq::new(i as u64)))
+            .collect();
+        Polynomial::new(coeffs)
+    }
+
+    /// Scale the polynomial so that its leading coefficient is 1
+    pub fn monic(&self) -> Self {
+        match self.leading_coeff() {
+            Some(lc) => self.scale(lc.inv()),
+            None => Polynomial::zero(),
+        }
+    }
+
+    /// Multiply every coefficient by a field element
+    pub fn scale(&self, c: Fq<Q>) -> Self {
+        Polynomial::new(self.coeffs.iter().map(|&a| a.mul(c)).collect())
+    }
+
+    /// Euclidean division, returning (quotient, remainder)
+    pub fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        let divisor_degree = divisor
+            .degree()
+            .expect("Cannot divide by the zero polynomial");
+        let lc_inv = divisor.coeffs[divisor_degree].inv();
+
+        let mut remainder = self.coeffs.clone();
+        let quotient_len = remainder.len().saturating_sub(divisor_degree);
+        let mut quotient = vec![Fq::new(0); quotient_len];
+
+        // eliminate the leading term of the remainder one degree at a time
+        for shift in (0..quotient_len).rev() {
+            let top = shift.checked_add(divisor_degree).expect("degree overflow");
+            let factor = remainder[top].mul(lc_inv);
+            quotient[shift] = factor;
+            for (r, &d) in remainder[shift..=top].iter_mut().zip(&divisor.coeffs) {
+                *r = r.sub(factor.mul(d));
+            }
+        }
+
+        remainder.truncate(divisor_degree);
+        (Polynomial::new(quotient), Polynomial::new(remainder))
+    }
+
+    /// Remainder of the division by another polynomial
+    pub fn rem(&self, modulus: &Self) -> Self {
+        self.div_rem(modulus).1
+    }
+
+    /// Monic greatest common divisor of two polynomials
+    pub fn gcd(&self, other: &Self) -> Self {
+        let mut a = self.clone();
+        let mut b = other.clone();
+
+        while !b.is_zero() {
+            let r = a.rem(&b);
+            a = b;
+            b = r;
+        }
+
+        a.monic()
+    }
+
+    /// Raise the polynomial to a power modulo another polynomial
+    pub fn pow_mod(&self, exponent: u64, modulus: &Self) -> Self {
+        let mut result = Polynomial::constant(Fq::new(1)).rem(modulus);
+        let mut base = self.rem(modulus);
+        let mut exp = exponent;
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.mul(&base).rem(modulus);
+            }
+            base = base.mul(&base).rem(modulus);
+            exp >>= 1;
+        }
+
+        result
+    }
+
+    /// Distinct roots of the polynomial in Fq, in ascending order
+    ///
+    /// Uses Cantor–Zassenhaus: gcd with x^q − x isolates the product of the linear
+    /// factors, which is then split by gcds with (x + δ)^((q−1)/2) − 1.
+    pub fn roots(&self) -> Vec<Fq<Q>> {
+        assert!(!self.is_zero(), "Cannot find roots of the zero polynomial");
+
+        let f = self.monic();
+        if f.degree() == Some(0) {
+            return Vec::new();
+        }
+
+        let x = Polynomial::x();
+        let x_to_q = x.pow_mod(Q, &f);
+        let linear_part = f.gcd(&x_to_q.sub(&x));
+
+        let mut roots = Vec::new();
+        linear_part.split_linear_factors(&mut roots);
+        roots.sort_by_key(|r| r.value());
+        roots
+    }
+
+    /// Collect the roots of a monic product of distinct linear factors
+    fn split_linear_factors(&self, roots: &mut Vec<Fq<Q>>) {
+        match self.degree() {
+            None | Some(0) => return,
+            Some(1) => {
+                roots.push(Fq::new(0).sub(self.coeffs[0]));
+                return;
+            }
+            Some(_) => {}
+        }
+
+        if Q == 2 {
+            // (q−1)/2 = 0 gives no splitting, but there are only two candidates
+            roots.extend(
+                [Fq::new(0), Fq::new(1)]
+                    .into_iter()
+                    .filter(|r| self.evaluate(*r).value() == 0),
+            );
+            return;
+        }
+
+        let half = Q.checked_sub(1).expect("Q must be gt 1").checked_div(2);
+        let half = half.expect("division failed");
+        let one = Polynomial::constant(Fq::new(1));
+
+        // every pair of distinct roots is separated by some shift δ, so this terminates
+        for delta in 0..Q {
+            let shifted = Polynomial::new(vec![Fq::new(delta), Fq::new(1)]);
+            let candidate = shifted.pow_mod(half, self).sub(&one);
+            let factor = self.gcd(&candidate);
+
+            if factor
+                .degree()
+                .is_some_and(|d| d > 0 && Some(d) < self.degree())
+            {
+                let (cofactor, _) = self.div_rem(&factor);
+                factor.split_linear_factors(roots);
+                cofactor.monic().split_linear_factors(roots);
+                return;
+            }
+        }
+
+        unreachable!("distinct roots are always separated by some shift");
+    }
+
+    fn normalize(&mut self) {
+        while self.coeffs.last().is_some_and(|c| c.value() == 0) {
+            self.coeffs.pop();
+        }
+    }
+}
+
+impl<const Q: u64> Add for &Polynomial<Q> {
+    type Output = Polynomial<Q>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let len = self.coeffs.len().max(rhs.coeffs.len());
+        let coeffs = (0..len)
+            .map(|i| {
+                let a = self.coeffs.get(i).copied().unwrap_or(Fq::new(0));
+                let b = rhs.coeffs.get(i).copied().unwrap_or(Fq::new(0));
+                a.add(b)
+            })
+            .collect();
+        Polynomial::new(coeffs)
+    }
+}
+
+impl<const Q: u64> Sub for &Polynomial<Q> {
+    type Output = Polynomial<Q>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let len = self.coeffs.len().max(rhs.coeffs.len());
+        let coeffs = (0..len)
+            .map(|i| {
+                let a = self.coeffs.get(i).copied().unwrap_or(Fq::new(0));
+                let b = rhs.coeffs.get(i).copied().unwrap_or(Fq::new(0));
+                a.sub(b)
+            })
+            .collect();
+        Polynomial::new(coeffs)
+    }
+}
+
+impl<const Q: u64> Mul for &Polynomial<Q> {
+    type Output = Polynomial<Q>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        if self.is_zero() || rhs.is_zero() {
+            return Polynomial::zero();
+        }
+
+        let len = self
+            .coeffs
+            .len()
+            .checked_add(rhs.coeffs.len())
+            .and_then(|l| l.checked_sub(1))
+            .expect("degree overflow");
+        let mut coeffs = vec![Fq::new(0); len];
+
+        for (i, &a) in self.coeffs.iter().enumerate() {
+            for (c, &b) in coeffs[i..].iter_mut().zip(&rhs.coeffs) {
+                *c = c.add(a.mul(b));
+            }
+        }
+
+        Polynomial::new(coeffs)
+    }
+}
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type F11 = Fq<11>;
+
+    fn poly(coeffs: &[u64]) -> Polynomial<11> {
+        Polynomial::new(coeffs.iter().map(|c| F11::new(*c)).collect())
+    }
+
+    #[test]
+    fn new__strips_leading_zeros() {
+        let p = poly(&[1, 2, 0, 0]);
+        assert_eq!(p.degree(), Some(1));
+        assert_eq!(poly(&[0, 0]).degree(), None);
+        assert!(poly(&[0, 0]).is_zero());
+    }
+
+    #[test]
+    fn add_sub__computes_correctly() {
+        let p = poly(&[1, 2, 3]); // 3x² + 2x + 1
+        let q = poly(&[10, 9, 8]); // 8x² + 9x + 10
+
+        // 11x² + 11x + 11 ≡ 0 (mod 11)
+        assert!(p.add(&q).is_zero());
+        // -5x² - 7x - 9 ≡ 6x² + 4x + 2 (mod 11)
+        assert_eq!(p.sub(&q), poly(&[2, 4, 6]));
+    }
+
+    #[test]
+    fn mul__computes_correctly() {
+        // (x + 1)(x - 1) = x² - 1
+        let p = poly(&[1, 1]);
+        let q = poly(&[10, 1]);
+        assert_eq!(p.mul(&q), poly(&[10, 0, 1]));
+        assert!(p.mul(&Polynomial::zero()).is_zero());
+    }
+
+    #[test]
+    fn div_rem__reconstructs_dividend() {
+        let dividend = poly(&[3, 0, 7, 5, 1]);
+        let divisor = poly(&[2, 4, 3]);
+
+        let (quotient, remainder) = dividend.div_rem(&divisor);
+        assert!(remainder.degree() < divisor.degree());
+        assert_eq!(quotient.mul(&divisor).add(&remainder), dividend);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot divide by the zero polynomial")]
+    fn div_rem__should_panic_when_dividing_by_zero() {
+        poly(&[1, 2]).div_rem(&Polynomial::zero());
+    }
+
+    #[test]
+    fn gcd__returns_monic_common_factor() {
+        // (x - 2)(x - 3) and (x - 3)(x - 5) share x - 3
+        let p = poly(&[2, 1]).mul(&poly(&[8, 1])).scale(F11::new(4));
+        let q = poly(&[8, 1]).mul(&poly(&[6, 1]));
+        assert_eq!(p.gcd(&q), poly(&[8, 1]));
+    }
+
+    #[test]
+    fn evaluate_and_derivative__compute_correctly() {
+        // 2x³ + x + 5
+        let p = poly(&[5, 1, 0, 2]);
+        assert_eq!(p.evaluate(F11::new(3)).value(), 7); // 54 + 3 + 5 = 62 ≡ 7 (mod 11)
+        // 6x² + 1
+        assert_eq!(p.derivative(), poly(&[1, 0, 6]));
+    }
+
+    #[test]
+    fn roots__finds_distinct_roots() {
+        // (x - 1)(x - 4)²(x - 9)(x² + 1), where x² + 1 is irreducible mod 11
+        let p = poly(&[10, 1])
+            .mul(&poly(&[7, 1]))
+            .mul(&poly(&[7, 1]))
+            .mul(&poly(&[2, 1]))
+            .mul(&poly(&[1, 0, 1]));
+
+        let roots: Vec<u64> = p.roots().iter().map(|r| r.value()).collect();
+        assert_eq!(roots, vec![1, 4, 9]);
+    }
+
+    #[test]
+    fn roots__proptest() {
+        const P: u64 = 7919;
+        type F = Fq<P>;
+
+        proptest::proptest!(|(r1 in 0..P, r2 in 0..P, r3 in 0..P)| {
+            let p = [r1, r2, r3].iter().fold(Polynomial::constant(F::new(1)), |acc, r| {
+                acc.mul(&Polynomial::new(vec![F::new(0).sub(F::new(*r)), F::new(1)]))
+            });
+
+            let mut expected = vec![r1, r2, r3];
+            expected.sort();
+            expected.dedup();
+
+            let roots: Vec<u64> = p.roots().iter().map(|r| r.value()).collect();
+            assert_eq!(roots, expected);
+        });
+    }
+}
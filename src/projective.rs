@@ -1,17 +1,51 @@
 //! Projective implementation of a twisted Hessian curve
 
+#[cfg(feature = "alloc")]
+use crate::msm;
 use crate::{
+    affine::Affine,
     field::Fq,
     ring::RingElement,
+    scalar::Scalar,
+    wnaf::{
+        MAX_WIDTH,
+        Wnaf,
+    },
 };
-use core::ops::{
-    Add,
-    Mul,
-    Sub,
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::{
+    hash::{
+        Hash,
+        Hasher,
+    },
+    ops::{
+        Add,
+        Mul,
+        Sub,
+    },
 };
 
-/// Represents a point [X:Y:Z] in projective coordinates on a twisted Hessian curve
+/// Number of precomputed odd multiples needed by the widest wNAF window
+const WNAF_TABLE_SIZE: usize = 1 << (MAX_WIDTH - 2);
+
+/// Algorithm used for variable-base scalar multiplication
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScalarMulMethod {
+    /// Binary double-and-add, see [`Projective::scalar_mul`]
+    DoubleAndAdd,
+    /// Windowed signed-digit method with the given wNAF width, see
+    /// [`Projective::scalar_mul_wnaf`]
+    Wnaf(u32),
+}
+
+/// Represents a point [X:Y:Z] in projective coordinates on a twisted Hessian curve
+///
+/// `==` and [`Hash`] compare the coordinates scaled to make the first unit one equal
+/// to 1, i.e. points up to scaling by a unit of Fq[ε], as does [`Projective::is_equal`].
+/// Triples without a unit coordinate, such as [0:0:0] or [ε:0:0], are not points and
+/// are only equal to each other.
+#[derive(Debug, Clone, Copy)]
 pub struct Projective<const Q: u64> {
     x: RingElement<Q>,
     y: RingElement<Q>,
@@ -20,7 +54,7 @@ pub struct Projective<const Q: u64> {
 
 impl<const Q: u64> Projective<Q> {
     /// Create a new projective point [X:Y:Z]
-    pub fn new(x: RingElement<Q>, y: RingElement<Q>, z: RingElement<Q>) -> Self {
+    pub const fn new(x: RingElement<Q>, y: RingElement<Q>, z: RingElement<Q>) -> Self {
         Projective { x, y, z }
     }
 
@@ -57,45 +91,16 @@ impl<const Q: u64> Projective<Q> {
         Q
     }
 
-    /// Check if this is the identity point
+    /// Check if this is the identity point, i.e. [0:-u:u] for some unit u
     pub fn is_identity(&self) -> bool {
-        self.x.constant().value() == 0
-            && self.y.constant().value() == self.modulus().checked_sub(1).unwrap()
-            && self.z.constant().value() == 1
+        let zero = RingElement::from_field(Fq::new(0));
+
+        self.x == zero && self.y.add(self.z) == zero && self.z.is_invertible()
     }
 
-    /// Check if a point is "projectively equal" to another
+    /// Check if a point is "projectively equal" to another, the same relation as `==`
     pub fn is_equal(&self, other: &Self) -> bool {
-        let self_is_zero = self.x.constant().value() == 0
-            && self.x.epsilon_coeff().value() == 0
-            && self.y.constant().value() == 0
-            && self.y.epsilon_coeff().value() == 0
-            && self.z.constant().value() == 0
-            && self.z.epsilon_coeff().value() == 0;
-
-        let other_is_zero = other.x.constant().value() == 0
-            && other.x.epsilon_coeff().value() == 0
-            && other.y.constant().value() == 0
-            && other.y.epsilon_coeff().value() == 0
-            && other.z.constant().value() == 0
-            && other.z.epsilon_coeff().value() == 0;
-
-        if self_is_zero || other_is_zero {
-            panic!("Attempted equality check with invalid point [0:0:0]");
-        }
-
-        // two projective points [X1:Y1:Z1] and [X2:Y2:Z2] are equal if
-        // X1*Z2 = X2*Z1 and Y1*Z2 = Y2*Z1 and Z1*X2 = Z2*X1
-        let x1z2 = self.x.mul(other.z);
-        let x2z1 = other.x.mul(self.z);
-
-        let y1z2 = self.y.mul(other.z);
-        let y2z1 = other.y.mul(self.z);
-
-        let z1x2 = self.z.mul(other.x);
-        let z2x1 = other.z.mul(self.x);
-
-        x1z2 == x2z1 && y1z2 == y2z1 && z1x2 == z2x1
+        self == other
     }
 
     /// Check if a point lies on a twisted Hessian curve aX³ + Y³ + Z³ = dXYZ
@@ -124,16 +129,96 @@ impl<const Q: u64> Projective<Q> {
         axyz == dxyz
     }
 
+    /// Normalise the point to its canonical affine representative
+    pub fn to_affine(&self) -> Affine<Q> {
+        let (pivot, pivot_is_z) = self.normalization_pivot();
+        self.normalize_with(pivot.inv(), pivot_is_z)
+    }
+
+    /// Normalise many points at once, sharing a single ring inversion
+    #[cfg(feature = "alloc")]
+    pub fn batch_normalize(points: &[Self]) -> Vec<Affine<Q>> {
+        let pivots: Vec<_> = points.iter().map(Self::normalization_pivot).collect();
+
+        // Montgomery's trick: prefix products, one inversion, then unwind
+        let mut prefix = Vec::with_capacity(pivots.len());
+        let mut acc = RingElement::from_field(Fq::new(1));
+        for (pivot, _) in &pivots {
+            prefix.push(acc);
+            acc = acc.mul(*pivot);
+        }
+
+        let mut acc_inv = acc.inv();
+        let mut normalized = Vec::with_capacity(points.len());
+        for ((point, (pivot, pivot_is_z)), before) in
+            points.iter().zip(&pivots).zip(&prefix).rev()
+        {
+            normalized.push(point.normalize_with(acc_inv.mul(*before), *pivot_is_z));
+            acc_inv = acc_inv.mul(*pivot);
+        }
+
+        normalized.reverse();
+        normalized
+    }
+
+    /// The coordinate to scale to 1: Z when it is invertible, Y otherwise
+    fn normalization_pivot(&self) -> (RingElement<Q>, bool) {
+        if self.z.is_invertible() {
+            (self.z, true)
+        } else {
+            assert!(
+                self.y.is_invertible(),
+                "Point must have an invertible Y or Z coordinate"
+            );
+            (self.y, false)
+        }
+    }
+
+    fn normalize_with(&self, pivot_inv: RingElement<Q>, pivot_is_z: bool) -> Affine<Q> {
+        if pivot_is_z {
+            let x = self.x.mul(pivot_inv);
+            let y = self.y.mul(pivot_inv);
+
+            let minus_one = RingElement::from_field(Fq::new(Q.checked_sub(1).unwrap()));
+            if x == RingElement::from_field(Fq::new(0)) && y == minus_one {
+                Affine::Identity
+            } else {
+                Affine::Finite { x, y }
+            }
+        } else {
+            Affine::AtInfinity {
+                x: self.x.mul(pivot_inv),
+                z: self.z.mul(pivot_inv),
+            }
+        }
+    }
+
     /// Negate a point: -[X:Y:Z] = [X:Z:Y]
     pub fn negate(&self) -> Self {
         Projective::new(self.x, self.z, self.y)
     }
 
     /// Add two points on a twisted Hessian curve
+    ///
+    /// Complete addition law of Bernstein, Chuengsatiansup, Kohel and Lange: both the
+    /// standard and the rotated formula are evaluated and the rotated result is
+    /// selected, without branching, whenever the standard one vanishes modulo ε. Over
+    /// Fq at least one of the two is non-zero for every pair of points, so the selected
+    /// result always has an invertible coordinate over Fq[ε].
     pub fn add(&self, other: &Self, a: RingElement<Q>) -> Self {
-        // implementation of Algorithm 3.1 (1) from the paper
+        let standard = self.add_standard(other);
+        let rotated = self.add_rotated(other, a);
+
+        Projective::conditional_select(
+            &standard,
+            &rotated,
+            !standard.has_unit_coordinate(),
+        )
+    }
 
-        // this is weird though, hessian curve additions are supposed to have a unified formula
+    /// Formula (1) from Theorem 2.1, exceptional when P₁ - P₂ lies in the 3-torsion
+    /// subgroup {[0:-1:1], [0:-ω:1], [0:-ω²:1]}, in particular when doubling
+    fn add_standard(&self, other: &Self) -> Self {
         let x1_squared = self.x.mul(self.x);
         let x2_squared = other.x.mul(other.x);
         let y1_squared = self.y.mul(self.y);
@@ -141,7 +226,7 @@ impl<const Q: u64> Projective<Q> {
         let z1_squared = self.z.mul(self.z);
         let z2_squared = other.z.mul(other.z);
 
-        // formula (1): X₃ = X₁²Y₂Z₂ - X₂²Y₁Z₁
+        // X₃ = X₁²Y₂Z₂ - X₂²Y₁Z₁
         let x3 = x1_squared
             .mul(other.y)
             .mul(other.z)
@@ -159,78 +244,247 @@ impl<const Q: u64> Projective<Q> {
             .mul(other.z)
             .sub(y2_squared.mul(self.x).mul(self.z));
 
-        let is_zero = x3.constant().value() == 0
-            && x3.epsilon_coeff().value() == 0
-            && y3.constant().value() == 0
-            && y3.epsilon_coeff().value() == 0
-            && z3.constant().value() == 0
-            && z3.epsilon_coeff().value() == 0;
-
-        if is_zero {
-            // formula (2) from Theorem 2.1
-            // X'₃ = Z₂²X₁Z₁ - Y₁²X₂Y₂
-            let x3_prime = z2_squared
-                .mul(self.x)
-                .mul(self.z)
-                .sub(y1_squared.mul(other.x).mul(other.y));
-
-            // Y'₃ = Y₂²Y₁Z₁ - aX₁²X₂Z₂
-            let y3_prime = y2_squared
-                .mul(self.y)
-                .mul(self.z)
-                .sub(a.mul(x1_squared).mul(other.x).mul(other.z));
-
-            // Z'₃ = aX₂²X₁Y₁ - Z₁²Y₂Z₂
-            let z3_prime = a
-                .mul(x2_squared)
-                .mul(self.x)
-                .mul(self.y)
-                .sub(z1_squared.mul(other.y).mul(other.z));
-
-            // if this also gives (0,0,0), invalid point
-            let is_zero_prime = x3_prime.constant().value() == 0
-                && x3_prime.epsilon_coeff().value() == 0
-                && y3_prime.constant().value() == 0
-                && y3_prime.epsilon_coeff().value() == 0
-                && z3_prime.constant().value() == 0
-                && z3_prime.epsilon_coeff().value() == 0;
-
-            if is_zero_prime {
-                panic!("Both addition formulas resulted in invalid point [0:0:0]");
-            }
+        Projective::new(x3, y3, z3)
+    }
 
-            Projective::new(x3_prime, y3_prime, z3_prime)
-        } else {
-            Projective::new(x3, y3, z3)
-        }
+    /// Formula (2) from Theorem 2.1, the rotated addition law, exceptional only when a
+    /// is a cube and P₁ + P₂ is one of the nine inflection points
+    fn add_rotated(&self, other: &Self, a: RingElement<Q>) -> Self {
+        let x1_squared = self.x.mul(self.x);
+        let x2_squared = other.x.mul(other.x);
+        let y1_squared = self.y.mul(self.y);
+        let y2_squared = other.y.mul(other.y);
+        let z1_squared = self.z.mul(self.z);
+        let z2_squared = other.z.mul(other.z);
+
+        // X'₃ = Z₂²X₁Z₁ - Y₁²X₂Y₂
+        let x3 = z2_squared
+            .mul(self.x)
+            .mul(self.z)
+            .sub(y1_squared.mul(other.x).mul(other.y));
+
+        // Y'₃ = Y₂²Y₁Z₁ - aX₁²X₂Z₂
+        let y3 = y2_squared
+            .mul(self.y)
+            .mul(self.z)
+            .sub(a.mul(x1_squared).mul(other.x).mul(other.z));
+
+        // Z'₃ = aX₂²X₁Y₁ - Z₁²Y₂Z₂
+        let z3 = a
+            .mul(x2_squared)
+            .mul(self.x)
+            .mul(self.y)
+            .sub(z1_squared.mul(other.y).mul(other.z));
+
+        Projective::new(x3, y3, z3)
     }
 
-    /// Double a point on a twisted Hessian curve (specialized point addition)
+    /// Check if at least one coordinate is invertible, i.e. [X:Y:Z] is a valid point
+    /// over Fq[ε] rather than zero or a multiple of ε
+    fn has_unit_coordinate(&self) -> bool {
+        (self.x.constant().value()
+            | self.y.constant().value()
+            | self.z.constant().value())
+            != 0
+    }
+
+    /// Select `a` if `choice` is false and `b` if it is true, without branching
+    pub fn conditional_select(a: &Self, b: &Self, choice: bool) -> Self {
+        Projective::new(
+            RingElement::conditional_select(&a.x, &b.x, choice),
+            RingElement::conditional_select(&a.y, &b.y, choice),
+            RingElement::conditional_select(&a.z, &b.z, choice),
+        )
+    }
+
+    /// Double a point on a twisted Hessian curve
+    ///
+    /// This is the rotated formula with P₁ = P₂, which is also what the complete
+    /// addition law selects when doubling. It has no exceptional points.
     pub fn double(&self, a: RingElement<Q>) -> Self {
-        self.add(self, a)
+        let ax_cubed = a.mul(self.x).mul(self.x).mul(self.x);
+        let y_cubed = self.y.mul(self.y).mul(self.y);
+        let z_cubed = self.z.mul(self.z).mul(self.z);
+
+        // X₃ = X(Z³ - Y³), Y₃ = Z(Y³ - aX³), Z₃ = Y(aX³ - Z³)
+        Projective::new(
+            self.x.mul(z_cubed.sub(y_cubed)),
+            self.z.mul(y_cubed.sub(ax_cubed)),
+            self.y.mul(ax_cubed.sub(z_cubed)),
+        )
+    }
+
+    /// Triple a point on a twisted Hessian curve
+    ///
+    /// Obtained from formula (1) applied to P and 2P after dividing out the common
+    /// factor X, with A = aX³, B = Y³ and C = Z³. This is a standalone primitive:
+    /// scalar multiplication only doubles, as a balanced-ternary chain built on
+    /// tripling benched slower than double-and-add.
+    pub fn triple(&self, a: RingElement<Q>) -> Self {
+        let xyz = self.x.mul(self.y).mul(self.z);
+        let a3 = a.mul(self.x).mul(self.x).mul(self.x);
+        let b3 = self.y.mul(self.y).mul(self.y);
+        let c3 = self.z.mul(self.z).mul(self.z);
+
+        let aa = a3.mul(a3);
+        let bb = b3.mul(b3);
+        let cc = c3.mul(c3);
+        let ab = a3.mul(b3);
+        let ac = a3.mul(c3);
+        let bc = b3.mul(c3);
+        let three_abc = RingElement::from_field(Fq::new(3)).mul(ab).mul(c3);
+
+        // X₃ = XYZ(A² + B² + C² - AB - AC - BC)
+        let x3 = xyz.mul(aa.add(bb).add(cc).sub(ab).sub(ac).sub(bc));
+        // Y₃ = A²B + AC² + B²C - 3ABC
+        let y3 = aa.mul(b3).add(ac.mul(c3)).add(bb.mul(c3)).sub(three_abc);
+        // Z₃ = A²C + AB² + BC² - 3ABC
+        let z3 = aa.mul(c3).add(ab.mul(b3)).add(bc.mul(c3)).sub(three_abc);
+
+        let tripled = Projective::new(x3, y3, z3);
+        if tripled.has_unit_coordinate() {
+            tripled
+        } else {
+            // not reached for points on the curve, but keep the generic path as backup
+            self.add(&self.double(a), a)
+        }
     }
 
     /// Multiply a point by a scalar using double-and-add algorithm
-    pub fn scalar_mul(&self, scalar: u64, a: RingElement<Q>) -> Self {
-        // TODO: optimize using msm
+    pub fn scalar_mul(&self, scalar: impl Into<Scalar>, a: RingElement<Q>) -> Self {
+        let scalar = scalar.into();
         let mut result = Projective::identity();
         let mut temp = *self;
-        let mut k = scalar;
 
-        while k > 0 {
-            if k & 1 == 1 {
+        for i in 0..scalar.bits() {
+            if scalar.bit(i) {
                 result = result.add(&temp, a);
             }
             temp = temp.double(a);
-            k >>= 1;
         }
 
         result
     }
 
+    /// Multiply a point by a scalar using a width-w NAF and a table of odd multiples
+    ///
+    /// Negation is free on twisted Hessian curves, so negative digits cost nothing
+    /// extra and only 2^(w-2) multiples P, 3P, ..., (2^(w-1) - 1)P are precomputed.
+    pub fn scalar_mul_wnaf(
+        &self,
+        scalar: impl Into<Scalar>,
+        width: u32,
+        a: RingElement<Q>,
+    ) -> Self {
+        let wnaf = Wnaf::new(&scalar.into(), width);
+
+        let table_len = (1usize << width) >> 2;
+        let mut table = [Projective::identity(); WNAF_TABLE_SIZE];
+        let double = self.double(a);
+        table[0] = *self;
+        for i in 1..table_len {
+            let previous = table[i.checked_sub(1).expect("qed")];
+            table[i] = previous.add(&double, a);
+        }
+
+        let mut result = Projective::identity();
+        for &digit in wnaf.digits().iter().rev() {
+            result = result.double(a);
+
+            let index = usize::from(digit.unsigned_abs() >> 1);
+            if digit > 0 {
+                result = result.add(&table[index], a);
+            } else if digit < 0 {
+                result = result.add(&table[index].negate(), a);
+            }
+        }
+
+        result
+    }
+
+    /// Multiply a point by a scalar with the chosen algorithm
+    pub fn scalar_mul_with(
+        &self,
+        scalar: impl Into<Scalar>,
+        method: ScalarMulMethod,
+        a: RingElement<Q>,
+    ) -> Self {
+        match method {
+            ScalarMulMethod::DoubleAndAdd => self.scalar_mul(scalar, a),
+            ScalarMulMethod::Wnaf(width) => self.scalar_mul_wnaf(scalar, width, a),
+        }
+    }
+
+    /// Compute Σ kᵢ·Pᵢ, using Straus' method for small inputs and Pippenger's for
+    /// large ones (see [`crate::msm`])
+    #[cfg(feature = "alloc")]
+    pub fn multi_scalar_mul(
+        points: &[Self],
+        scalars: &[Scalar],
+        a: RingElement<Q>,
+    ) -> Self {
+        if points.len() <= msm::STRAUS_THRESHOLD {
+            msm::straus(points, scalars, a)
+        } else {
+            msm::pippenger(points, scalars, a)
+        }
+    }
+
+    /// Multiply a point by a scalar with a constant-time Montgomery ladder
+    ///
+    /// The ladder always runs for the bit length of `order` and swaps its registers
+    /// with masks instead of branching on the scalar bits. The scalar must fit in that
+    /// many bits, so reduce it modulo the order first.
+    pub fn scalar_mul_ct(
+        &self,
+        scalar: impl Into<Scalar>,
+        order: impl Into<Scalar>,
+        a: RingElement<Q>,
+    ) -> Self {
+        let scalar = scalar.into();
+        let bits = order.into().bits();
+        assert!(
+            scalar.bits() <= bits,
+            "Scalar must not be wider than the group order"
+        );
+
+        // invariant: r1 - r0 = P
+        let mut r0 = Projective::identity();
+        let mut r1 = *self;
+        let mut swapped = false;
+
+        for i in (0..bits).rev() {
+            let bit = scalar.bit(i);
+            Projective::conditional_swap(&mut r0, &mut r1, swapped ^ bit);
+            swapped = bit;
+
+            r1 = r0.add(&r1, a);
+            r0 = r0.double(a);
+        }
+        Projective::conditional_swap(&mut r0, &mut r1, swapped);
+
+        r0
+    }
+
+    /// Swap two points if `choice` is true, without branching
+    pub fn conditional_swap(p: &mut Self, q: &mut Self, choice: bool) {
+        let new_p = Projective::conditional_select(p, q, choice);
+        let new_q = Projective::conditional_select(q, p, choice);
+        *p = new_p;
+        *q = new_q;
+    }
+
+    /// Scale the first invertible coordinate to 1, or `None` if there is none
+    fn canonical_coordinates(&self) -> Option<[RingElement<Q>; 3]> {
+        let coordinates = [self.x, self.y, self.z];
+        let pivot = coordinates.iter().find(|c| c.is_invertible())?.inv();
+
+        Some(coordinates.map(|c| c.mul(pivot)))
+    }
+
     /// Verify a & d
     pub fn verify_curve_constraints(a: RingElement<Q>, d: RingElement<Q>) -> bool {
-        let twenty_seven = RingElement::from_field(Fq::<Q>::new(27 % Q));
+        let twenty_seven = RingElement::from_field(Fq::<Q>::new(27u64.rem_euclid(Q)));
         let twenty_seven_a = twenty_seven.mul(a);
 
         let d_squared = d.mul(d);
@@ -243,11 +497,34 @@ impl<const Q: u64> Projective<Q> {
     }
 }
 
+impl<const Q: u64> PartialEq for Projective<Q> {
+    fn eq(&self, other: &Self) -> bool {
+        // an equivalence that agrees with `Hash` and never panics, unlike the minors
+        self.canonical_coordinates() == other.canonical_coordinates()
+    }
+}
+
+impl<const Q: u64> Eq for Projective<Q> {}
+
+impl<const Q: u64> Hash for Projective<Q> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // without a unit coordinate every minor vanishes, so all such triples are equal
+        self.canonical_coordinates().hash(state);
+    }
+}
+
 #[allow(non_snake_case)]
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::field::Fq;
+    use crate::{
+        field::Fq,
+        test_support::{
+            all_points,
+            paper_curve,
+            paper_curve_and_generator,
+        },
+    };
 
     #[test]
     fn kats_paper_3_1() {
@@ -288,7 +565,7 @@ mod tests {
         assert!(p.is_on_curve(a, d), "P should be on the curve");
 
         // according to the paper, 4P = [1, 4, 3+2ε]
-        let four_p = p.scalar_mul(4, a);
+        let four_p = p.scalar_mul(4u64, a);
 
         let expected_4p_x = RingElement::from_field(field_1); // 1
         let expected_4p_y = RingElement::from_field(field_4); // 4
@@ -302,7 +579,7 @@ mod tests {
         );
 
         // according to the paper, 5P = [1, 3+2ε, 4+3ε]
-        let five_p = p.scalar_mul(5, a);
+        let five_p = p.scalar_mul(5u64, a);
 
         let expected_5p_x = RingElement::from_field(field_1); // 1
         let expected_5p_y = RingElement::new(field_3, field_2); // 3+2ε
@@ -316,7 +593,7 @@ mod tests {
         );
 
         // according to the paper, 35P = [1, 3, 2]
-        let thirtyfive_p = p.scalar_mul(35, a);
+        let thirtyfive_p = p.scalar_mul(35u64, a);
 
         let expected_35p_x = RingElement::from_field(field_1); // 1
         let expected_35p_y = RingElement::from_field(field_3); // 3
@@ -362,4 +639,247 @@ mod tests {
         // verify P is on the curve
         assert!(p.is_on_curve(a, d), "P should be on the curve");
     }
+
+    #[test]
+    fn add__is_complete_over_all_pairs() {
+        let curve = paper_curve();
+        let (a, d) = (curve.a(), curve.d());
+
+        let points = all_points(&curve);
+        assert_eq!(points.len(), 45);
+
+        for p in &points {
+            for q in &points {
+                let sum = p.add(q, a);
+
+                assert!(sum.has_unit_coordinate(), "P + Q must be a valid point");
+                assert!(sum.is_on_curve(a, d), "P + Q must be on the curve");
+                assert!(sum.is_equal(&q.add(p, a)), "addition must commute");
+
+                // whichever formula is not exceptional agrees with the result
+                for candidate in [p.add_standard(q), p.add_rotated(q, a)] {
+                    if candidate.has_unit_coordinate() {
+                        assert!(candidate.is_equal(&sum));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn add__satisfies_group_axioms_over_all_points() {
+        let curve = paper_curve();
+        let a = curve.a();
+
+        let points = all_points(&curve);
+        let identity = Projective::identity();
+
+        for p in &points {
+            assert!(p.add(&identity, a).is_equal(p));
+            assert!(p.add(&p.negate(), a).is_equal(&identity));
+
+            for q in &points {
+                let p_plus_q = p.add(q, a);
+                for r in &points {
+                    let left = p_plus_q.add(r, a);
+                    let right = p.add(&q.add(r, a), a);
+                    assert!(left.is_equal(&right), "addition must be associative");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn double_and_triple__match_generic_addition_over_all_points() {
+        let curve = paper_curve();
+        let a = curve.a();
+
+        for p in all_points(&curve) {
+            let doubled = p.double(a);
+            assert!(doubled.has_unit_coordinate());
+            assert!(doubled.is_equal(&p.add_rotated(&p, a)));
+
+            let tripled = p.triple(a);
+            assert!(tripled.has_unit_coordinate());
+            assert!(tripled.is_equal(&p.add(&p, a).add(&p, a)));
+        }
+    }
+
+    #[test]
+    fn triple__kats_paper_3_1() {
+        let (curve, p) = paper_curve_and_generator();
+        let a = curve.a();
+
+        // 15P = 3(5P) and 5P = [1, 3+2ε, 4+3ε]
+        let five_p = p.scalar_mul(5u64, a);
+        assert!(five_p.triple(a).is_equal(&p.scalar_mul(15u64, a)));
+
+        // 45P = 3(15P) = O
+        assert!(five_p.triple(a).triple(a).is_equal(&Projective::identity()));
+    }
+
+    #[test]
+    fn to_affine__is_canonical_over_all_points() {
+        type F5 = Fq<5>;
+
+        let unit = RingElement::new(F5::new(3), F5::new(4)); // 3+4ε
+
+        let points = all_points(&paper_curve());
+        let scaled: Vec<_> = points
+            .iter()
+            .map(|p| Projective::new(p.x.mul(unit), p.y.mul(unit), p.z.mul(unit)))
+            .collect();
+
+        let mut at_infinity = 0;
+        for (p, s) in points.iter().zip(&scaled) {
+            let affine = p.to_affine();
+            assert_eq!(affine, s.to_affine());
+            assert!(affine.to_projective().is_equal(p));
+            assert_eq!(affine.is_identity(), p.is_equal(&Projective::identity()));
+
+            if matches!(affine, Affine::AtInfinity { .. }) {
+                at_infinity += 1;
+            }
+        }
+
+        // [1:-1:0] is the only point with Z = 0 on the residue curve, with five lifts
+        assert_eq!(at_infinity, 5);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn batch_normalize__matches_to_affine() {
+        let points = all_points(&paper_curve());
+        let expected: Vec<_> = points.iter().map(Projective::to_affine).collect();
+
+        assert_eq!(Projective::batch_normalize(&points), expected);
+        assert!(Projective::<5>::batch_normalize(&[]).is_empty());
+    }
+
+    #[test]
+    fn scalar_mul_ct__matches_double_and_add() {
+        let (curve, p) = paper_curve_and_generator();
+        let a = curve.a();
+
+        for k in 0..64u64 {
+            assert!(p.scalar_mul_ct(k, 45u64, a).is_equal(&p.scalar_mul(k, a)));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Scalar must not be wider than the group order")]
+    fn scalar_mul_ct__should_panic_when_scalar_is_wider_than_order() {
+        let a = paper_curve().a();
+        Projective::<5>::identity().scalar_mul_ct(64u64, 45u64, a);
+    }
+
+    #[test]
+    fn scalar_mul_wnaf__matches_double_and_add() {
+        let (curve, p) = paper_curve_and_generator();
+        let a = curve.a();
+
+        for width in 2..=MAX_WIDTH {
+            for k in (0..200).chain([u64::MAX - 1, u64::MAX]).map(Scalar::from) {
+                let expected = p.scalar_mul(k, a);
+                assert!(p.scalar_mul_wnaf(k, width, a).is_equal(&expected));
+                assert!(
+                    p.scalar_mul_with(k, ScalarMulMethod::Wnaf(width), a)
+                        .is_equal(&expected)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn scalar_mul__reduces_wide_scalars_modulo_the_order() {
+        let (curve, p) = paper_curve_and_generator();
+        let a = curve.a();
+
+        // P has order 45, so only k mod 45 matters however wide k is
+        let order = Scalar::from(45u64);
+        for k in [Scalar::MAX, Scalar::from_limbs([7, 0, 1, 1 << 40])] {
+            let expected = p.scalar_mul(k.rem(&order), a);
+
+            assert!(p.scalar_mul(k, a).is_equal(&expected));
+            assert!(p.scalar_mul_wnaf(k, 5, a).is_equal(&expected));
+        }
+    }
+
+    #[test]
+    fn eq__respects_projective_equivalence() {
+        type F7 = Fq<7>;
+        let ring = |c, e| RingElement::new(F7::new(c), F7::new(e));
+
+        let p = Projective::new(ring(1, 0), ring(2, 0), ring(3, 0));
+        let scaled = Projective::new(ring(2, 0), ring(4, 0), ring(6, 0));
+        assert_eq!(p, scaled);
+
+        // scaling by the unit 1+ε
+        let unit = ring(1, 1);
+        let lifted = Projective::new(p.x.mul(unit), p.y.mul(unit), p.z.mul(unit));
+        assert_eq!(p, lifted);
+
+        // but [1:2:3+ε] is a different point
+        assert_ne!(p, Projective::new(ring(1, 0), ring(2, 0), ring(3, 1)));
+    }
+
+    #[test]
+    fn eq__is_an_equivalence_on_non_unit_triples() {
+        use std::collections::HashSet;
+        type F7 = Fq<7>;
+        let ring = |c, e| RingElement::new(F7::new(c), F7::new(e));
+        let (zero, one, eps) = (ring(0, 0), ring(1, 0), ring(0, 1));
+
+        let unit_triple = Projective::new(one, zero, zero);
+        let x_eps = Projective::new(eps, zero, zero);
+        let y_eps = Projective::new(zero, eps, zero);
+        let all_zero = Projective::new(zero, zero, zero);
+
+        // never panics, and the triples without a unit form one class
+        assert_ne!(unit_triple, x_eps);
+        assert_ne!(unit_triple, y_eps);
+        assert_ne!(unit_triple, all_zero);
+        assert_eq!(x_eps, y_eps);
+        assert_eq!(x_eps, all_zero);
+        assert!(x_eps.is_equal(&all_zero) && !all_zero.is_equal(&unit_triple));
+
+        let set: HashSet<_> = [unit_triple, x_eps, y_eps, all_zero].into_iter().collect();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&Projective::new(ring(3, 2), zero, zero)));
+        assert!(set.contains(&Projective::new(zero, zero, ring(0, 5))));
+    }
+
+    #[test]
+    fn hash__is_consistent_with_eq_over_all_points() {
+        use std::collections::HashSet;
+        type F5 = Fq<5>;
+
+        let points = all_points(&paper_curve());
+
+        let mut set: HashSet<_> = points.iter().copied().collect();
+        assert_eq!(set.len(), 45);
+
+        for unit in [(2, 0), (3, 4), (1, 1)] {
+            let unit = RingElement::new(F5::new(unit.0), F5::new(unit.1));
+            for p in &points {
+                let scaled = Projective::new(p.x.mul(unit), p.y.mul(unit), p.z.mul(unit));
+                assert!(set.contains(&scaled));
+                assert!(!set.insert(scaled));
+            }
+        }
+    }
+
+    #[test]
+    fn is_identity__is_projective() {
+        type F5 = Fq<5>;
+
+        let unit = RingElement::new(F5::new(3), F5::new(4)); // 3+4ε
+        let zero = RingElement::from_field(F5::new(0));
+        let eps = RingElement::new(F5::new(0), F5::new(1));
+
+        assert!(Projective::new(zero, zero.sub(unit), unit).is_identity());
+        assert!(!Projective::new(eps, zero.sub(unit), unit).is_identity());
+        assert!(!Projective::new(zero, zero.sub(unit), unit.add(eps)).is_identity());
+        assert!(!Projective::new(zero, zero.sub(eps), eps).is_identity());
+    }
 }
@@ -0,0 +1,149 @@
+//! Uniformly random points on a twisted Hessian curve
+//!
+//! A residue point is drawn by picking one of q + 1 slots and an index below 3: slot
+//! x < q stands for the points [x:y:1], the roots y of y³ - d₀xy + a₀x³ + 1, and slot q
+//! for the points [x:1:0] at infinity, the roots x of a₀x³ + 1. The draw is retried
+//! when the index exceeds the number of roots, so every point of E(Fq) comes out with
+//! the same probability.
+//!
+//! Its lift for a uniform parameter t is then uniform among the q points above it,
+//! which makes the result uniform on E(Fq[ε]).
+
+use crate::{
+    curve::TwistedHessianCurve,
+    field::Fq,
+    poly::Polynomial,
+    projective::Projective,
+    ring::RingElement,
+};
+use alloc::vec;
+use core::ops::{
+    Add,
+    Mul,
+    Sub,
+};
+use rand_core::RngCore;
+
+impl<const Q: u64> TwistedHessianCurve<Q> {
+    /// Draw a uniformly distributed point of the curve over Fq[ε]
+    pub fn random_point(&self, rng: &mut impl RngCore) -> Projective<Q> {
+        let residue = self.random_residue_point(rng);
+        let t = Fq::new(uniform_below(rng, Q));
+
+        self.lift_point(&residue, t)
+    }
+
+    /// Draw a uniformly distributed point of the residue curve over Fq
+    fn random_residue_point(&self, rng: &mut impl RngCore) -> Projective<Q> {
+        let (a0, d0) = (self.a().constant(), self.d().constant());
+        let (zero, one) = (Fq::new(0), Fq::new(1));
+        let embed = RingElement::from_field;
+
+        loop {
+            let slot = uniform_below(rng, Q.saturating_add(1));
+            let index = usize::try_from(uniform_below(rng, 3)).expect("index < 3");
+
+            let point = if slot < Q {
+                // y³ - d₀xy + a₀x³ + 1 for the finite points [x:y:1]
+                let x = Fq::new(slot);
+                let cubic = Polynomial::new(vec![
+                    a0.mul(x).mul(x).mul(x).add(one),
+                    zero.sub(d0.mul(x)),
+                    zero,
+                    one,
+                ]);
+
+                cubic
+                    .roots()
+                    .get(index)
+                    .map(|y| Projective::new(embed(x), embed(*y), embed(one)))
+            } else {
+                // a₀x³ + 1 for the points [x:1:0] at infinity
+                let cubic = Polynomial::new(vec![one, zero, zero, a0]);
+
+                cubic
+                    .roots()
+                    .get(index)
+                    .map(|x| Projective::new(embed(*x), embed(one), embed(zero)))
+            };
+
+            if let Some(point) = point {
+                return point;
+            }
+        }
+    }
+}
+
+/// Uniform integer in 0..bound by rejection sampling, without modulo bias
+fn uniform_below(rng: &mut impl RngCore, bound: u64) -> u64 {
+    // a multiple of bound, so that values below it are uniform modulo bound
+    let limit =
+        u64::MAX.saturating_sub(u64::MAX.checked_rem(bound).expect("bound is non-zero"));
+
+    loop {
+        let value = rng.next_u64();
+        if value < limit {
+            return value.checked_rem(bound).expect("bound is non-zero");
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::paper_curve_and_generator;
+    use rand::{
+        SeedableRng,
+        rngs::StdRng,
+    };
+    use std::collections::HashMap;
+
+    #[test]
+    fn random_point__lies_on_large_curves() {
+        const Q: u64 = 2_147_483_647;
+
+        let a = RingElement::new(Fq::new(2), Fq::new(7));
+        let d = RingElement::new(Fq::new(5), Fq::new(3));
+        let curve = TwistedHessianCurve::<Q>::new(a, d);
+        let mut rng = StdRng::seed_from_u64(46);
+
+        for _ in 0..20 {
+            let point = curve.random_point(&mut rng);
+            assert!(curve.contains(&point));
+            assert!(curve.reduce().contains(&point.reduce()));
+        }
+    }
+
+    #[test]
+    fn random_point__is_uniform_on_paper_3_1_curve() {
+        // E(F5[ε]) is cyclic of order 45
+        let (curve, generator) = paper_curve_and_generator();
+        let points: Vec<_> = (0..45u64)
+            .map(|k| curve.scalar_mul(&generator, k))
+            .collect();
+
+        let mut rng = StdRng::seed_from_u64(45);
+        let mut counts = HashMap::new();
+        for _ in 0..45 * 200 {
+            let point = curve.random_point(&mut rng);
+            let index = points.iter().position(|p| *p == point).unwrap();
+            *counts.entry(index).or_insert(0u32) += 1;
+        }
+
+        // 200 expected per point, with a standard deviation of about 14
+        assert_eq!(counts.len(), 45);
+        assert!(counts.values().all(|count| (130..=270).contains(count)));
+    }
+
+    #[test]
+    fn uniform_below__stays_in_range() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for bound in [1, 2, 3, 7, u64::MAX] {
+            for _ in 0..100 {
+                assert!(uniform_below(&mut rng, bound) < bound);
+            }
+        }
+    }
+}
@@ -0,0 +1,445 @@
+//! The decomposition E(Fq[ε]) ≅ E(Fq) × Fq
+//!
+//! Reducing coordinates modulo ε is a surjective homomorphism onto the residue curve
+//! a₀X³ + Y³ + Z³ = d₀XYZ over Fq. Its kernel is made of the points
+//! [εt : -1 - εd₀t/3 : 1] ≡ [0:-1:1], and t adds up under the group law, so the kernel
+//! is the additive group of Fq.
+//!
+//! The ε-component θ(P) = t(N·P)/N, with N = #E(Fq), is a homomorphism onto Fq that is
+//! the identity on the kernel. Together with the reduction it gives the isomorphism
+//! P ↦ (P mod ε, θ(P)), which needs q ∤ N.
+//!
+//! Each point of the residue curve has exactly q lifts, whose ε-parts solve a linear
+//! equation given by the partial derivatives of the curve equation.
+//!
+//! The residue curve and its points are represented over Fq[ε] with zero ε-parts.
+
+use crate::{
+    curve::TwistedHessianCurve,
+    field::Fq,
+    projective::Projective,
+    ring::RingElement,
+};
+use core::ops::{
+    Add,
+    Mul,
+    Sub,
+};
+
+impl<const Q: u64> TwistedHessianCurve<Q> {
+    /// Get the residue curve a₀X³ + Y³ + Z³ = d₀XYZ over Fq
+    pub fn reduce(&self) -> TwistedHessianCurve<Q> {
+        TwistedHessianCurve::new(
+            RingElement::from_field(self.a().constant()),
+            RingElement::from_field(self.d().constant()),
+        )
+    }
+
+    /// Get the point [εt : -1 - εd₀t/3 : 1] of the kernel of the reduction
+    pub fn kernel_point(&self, t: Fq<Q>) -> Projective<Q> {
+        assert!(Q != 3, "The kernel is parametrised by t only for q ≠ 3");
+
+        let zero = Fq::new(0);
+        let d0_t = self.d().constant().mul(t);
+
+        Projective::new(
+            RingElement::new(zero, t),
+            RingElement::new(
+                Fq::new(Q.saturating_sub(1)),
+                zero.sub(d0_t.mul(Fq::new(3).inv())),
+            ),
+            RingElement::from_field(Fq::new(1)),
+        )
+    }
+
+    /// Set up the decomposition of [`TwistedHessianCurve::decompose`], counting the
+    /// residue curve once for any number of points
+    pub fn decomposition(&self) -> Decomposition<Q> {
+        Decomposition::new(*self)
+    }
+
+    /// Get the ε-component θ(P) = t(N·P)/N of a point, N being the residue order
+    ///
+    /// This counts the residue curve on every call, see [`Decomposition`] for many
+    /// points.
+    pub fn epsilon_component(&self, point: &Projective<Q>) -> Fq<Q> {
+        self.decomposition().epsilon_component(point)
+    }
+
+    /// Split a point into its reduction on [`TwistedHessianCurve::reduce`] and its
+    /// ε-component
+    pub fn decompose(&self, point: &Projective<Q>) -> (Projective<Q>, Fq<Q>) {
+        self.decomposition().decompose(point)
+    }
+
+    /// Get the unique point with the given reduction and ε-component, inverting
+    /// [`TwistedHessianCurve::decompose`]
+    pub fn compose(&self, residue: &Projective<Q>, t: Fq<Q>) -> Projective<Q> {
+        self.decomposition().compose(residue, t)
+    }
+
+    /// Lift a point of the residue curve to the point of this curve whose ε-parts are
+    /// solved from the partial derivatives of F = aX³ + Y³ + Z³ - dXYZ
+    ///
+    /// With P₀ = [X₀:Y₀:Z₀], a lift P₀ + εV is on the curve iff F₁(P₀) + ∇F(P₀)·V = 0,
+    /// F₁ being the ε-part of F from those of a and d, and V is defined up to multiples
+    /// of P₀. The ε-part is fixed to zero on the first coordinate k with a non-zero
+    /// residue, and to `t` on one of the other two, while the remaining coordinate i,
+    /// chosen with ∂F/∂i(P₀) ≠ 0, is solved for. Over t ∈ Fq this gives each of the q
+    /// lifts exactly once.
+    pub fn lift_point(&self, residue: &Projective<Q>, t: Fq<Q>) -> Projective<Q> {
+        assert!(
+            self.reduce().contains(residue),
+            "Point must be on the residue curve"
+        );
+
+        let (a, d) = (self.a(), self.d());
+        let (x, y, z) = (residue.x(), residue.y(), residue.z());
+        let three = RingElement::from_field(Fq::new(3));
+
+        // F(P₀) is a multiple of ε since P₀ is on the residue curve
+        let value = a
+            .mul(x)
+            .mul(x)
+            .mul(x)
+            .add(y.mul(y).mul(y))
+            .add(z.mul(z).mul(z))
+            .sub(d.mul(x).mul(y).mul(z))
+            .epsilon_coeff();
+
+        let partials = [
+            three.mul(a).mul(x).mul(x).sub(d.mul(y).mul(z)),
+            three.mul(y).mul(y).sub(d.mul(x).mul(z)),
+            three.mul(z).mul(z).sub(d.mul(x).mul(y)),
+        ]
+        .map(|partial| partial.constant());
+
+        let coordinates = [x, y, z];
+        let k = coordinates
+            .iter()
+            .position(|c| c.constant().value() != 0)
+            .expect("a point has a non-zero coordinate");
+        // ∇F(P₀)·P₀ = 3F(P₀) = 0, so by smoothness another partial is non-zero
+        let i = (0..3)
+            .find(|&i| i != k && partials[i].value() != 0)
+            .expect("the residue curve is smooth");
+        let j = (0..3)
+            .find(|&j| j != k && j != i)
+            .expect("three coordinates");
+
+        // ∂ᵢF·vᵢ + ∂ⱼF·t = -F₁
+        let solved = Fq::new(0).sub(value.add(partials[j].mul(t)).mul(partials[i].inv()));
+
+        let mut epsilon_parts = [Fq::new(0); 3];
+        epsilon_parts[i] = solved;
+        epsilon_parts[j] = t;
+        let [x, y, z] = core::array::from_fn(|n| {
+            RingElement::new(coordinates[n].constant(), epsilon_parts[n])
+        });
+
+        Projective::new(x, y, z)
+    }
+
+    /// Find the parameter t with [`TwistedHessianCurve::lift_point`]`(residue, t)` equal
+    /// to `point`, or `None` if `point` does not reduce to `residue`
+    pub fn lift_parameter(
+        &self,
+        residue: &Projective<Q>,
+        point: &Projective<Q>,
+    ) -> Option<Fq<Q>> {
+        if !self.contains(point) || point.reduce() != *residue {
+            return None;
+        }
+
+        // the lifts are affine in t, with ε-parts E(t) = E(0) + tD where D has a 1 at the
+        // coordinate set to t and a 0 at the first coordinate k with a non-zero residue
+        let base = self.lift_point(residue, Fq::new(0));
+        let step = self.lift_point(residue, Fq::new(1));
+        let coordinates = |p: &Projective<Q>| [p.x(), p.y(), p.z()];
+        let k = coordinates(residue)
+            .iter()
+            .position(|c| c.constant().value() != 0)
+            .expect("a point has a non-zero coordinate");
+        let m = (0..3)
+            .find(|&m| {
+                coordinates(&step)[m]
+                    .sub(coordinates(&base)[m])
+                    .epsilon_coeff()
+                    == Fq::new(1)
+            })
+            .expect("one coordinate has ε-part t");
+
+        // scale the point to agree with the residue coordinates, the k-th ε-part being 0
+        let scale = coordinates(residue)[k].mul(coordinates(point)[k].inv());
+        let scaled = coordinates(point)[m].mul(scale);
+        let t = scaled
+            .epsilon_coeff()
+            .sub(coordinates(&base)[m].epsilon_coeff());
+
+        (self.lift_point(residue, t) == *point).then_some(t)
+    }
+
+    /// Get the q points of this curve that reduce to a point of the residue curve, see
+    /// [`TwistedHessianCurve::lift_point`]
+    pub fn all_lifts(
+        &self,
+        residue: &Projective<Q>,
+    ) -> impl Iterator<Item = Projective<Q>> {
+        let curve = *self;
+        let residue = *residue;
+
+        (0..Q).map(move |t| curve.lift_point(&residue, Fq::new(t)))
+    }
+}
+
+/// The isomorphism P ↦ (P mod ε, θ(P)) of a curve, with the residue order N counted
+/// once
+#[derive(Debug, Clone, Copy)]
+pub struct Decomposition<const Q: u64> {
+    curve: TwistedHessianCurve<Q>,
+    residue_order: u64,
+}
+
+impl<const Q: u64> Decomposition<Q> {
+    /// Count the residue curve of `curve`, which must not have a multiple of q points
+    pub fn new(curve: TwistedHessianCurve<Q>) -> Self {
+        let residue_order = curve.residue_order();
+        assert!(
+            residue_order.checked_rem(Q) != Some(0),
+            "The residue curve must not have a multiple of q points"
+        );
+
+        Decomposition {
+            curve,
+            residue_order,
+        }
+    }
+
+    /// Get the curve
+    pub fn curve(&self) -> TwistedHessianCurve<Q> {
+        self.curve
+    }
+
+    /// Get the residue order N = #E(Fq)
+    pub fn residue_order(&self) -> u64 {
+        self.residue_order
+    }
+
+    /// Get the ε-component θ(P) = t(N·P)/N of a point
+    pub fn epsilon_component(&self, point: &Projective<Q>) -> Fq<Q> {
+        assert!(self.curve.contains(point), "Point must be on the curve");
+
+        let order = self.residue_order;
+        let kernel = point.scalar_mul(order, self.curve.a());
+        // t is the ε-part of X/Z, and Z is a unit on the kernel
+        let t = kernel.x().mul(kernel.z().inv()).epsilon_coeff();
+
+        t.mul(Fq::new(order).inv())
+    }
+
+    /// Split a point into its reduction and its ε-component
+    pub fn decompose(&self, point: &Projective<Q>) -> (Projective<Q>, Fq<Q>) {
+        (point.reduce(), self.epsilon_component(point))
+    }
+
+    /// Get the unique point with the given reduction and ε-component, inverting
+    /// [`Decomposition::decompose`]
+    pub fn compose(&self, residue: &Projective<Q>, t: Fq<Q>) -> Projective<Q> {
+        let curve = &self.curve;
+        assert!(
+            curve.reduce().contains(residue),
+            "Point must be on the residue curve"
+        );
+
+        // any lift, corrected by a kernel point to the requested ε-component
+        let lift = curve.lift_point(residue, Fq::new(0));
+        let correction = t.sub(self.epsilon_component(&lift));
+
+        lift.add(&curve.kernel_point(correction), curve.a())
+    }
+}
+
+impl<const Q: u64> Projective<Q> {
+    /// Reduce the coordinates modulo ε, giving a point of the residue curve
+    pub fn reduce(&self) -> Self {
+        Projective::new(
+            RingElement::from_field(self.x().constant()),
+            RingElement::from_field(self.y().constant()),
+            RingElement::from_field(self.z().constant()),
+        )
+    }
+}
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::paper_curve_and_generator;
+
+    #[test]
+    fn decompose__kats_paper_3_1() {
+        let (curve, generator) = paper_curve_and_generator();
+        let residue = curve.reduce();
+
+        // 45 = 9·5: P mod ε has order 9 and 9P is a kernel point of order 5
+        assert_eq!(curve.residue_order(), 9);
+        let (reduced, t) = curve.decompose(&generator);
+        assert_eq!(residue.point_order(&reduced), 9);
+        assert_ne!(t.value(), 0);
+
+        let nine_p = generator.scalar_mul(9u64, curve.a());
+        assert_eq!(nine_p.reduce(), Projective::identity());
+        assert_eq!(curve.point_order(&nine_p), 5);
+        assert_eq!(curve.epsilon_component(&nine_p), t.mul(Fq::new(9)));
+    }
+
+    #[test]
+    fn decompose__is_an_isomorphism_over_all_points() {
+        let (curve, generator) = paper_curve_and_generator();
+        let residue = curve.reduce();
+        let decomposition = curve.decomposition();
+        let points: [Projective<5>; 45] =
+            core::array::from_fn(|k| generator.scalar_mul(k as u64, curve.a()));
+
+        for p in &points {
+            let (p0, tp) = decomposition.decompose(p);
+            assert!(residue.contains(&p0));
+            assert_eq!(decomposition.compose(&p0, tp), *p);
+            assert_eq!(curve.decompose(p), (p0, tp));
+
+            for q in &points {
+                let (q0, tq) = decomposition.decompose(q);
+                let (sum0, t_sum) = decomposition.decompose(&curve.add(p, q));
+
+                assert_eq!(sum0, residue.add(&p0, &q0));
+                assert_eq!(t_sum, tp.add(tq));
+            }
+        }
+    }
+
+    #[test]
+    fn kernel_point__is_additive() {
+        type F11 = Fq<11>;
+
+        let a = RingElement::new(F11::new(1), F11::new(2)); // 1+2ε
+        let d = RingElement::new(F11::new(2), F11::new(1)); // 2+ε
+        let curve = TwistedHessianCurve::new(a, d);
+
+        for t in (0..11).map(F11::new) {
+            let point = curve.kernel_point(t);
+            assert!(curve.contains(&point));
+            assert_eq!(point.reduce(), Projective::identity());
+            assert_eq!(curve.epsilon_component(&point), t);
+            assert_eq!(
+                curve.add(&point, &curve.kernel_point(F11::new(3))),
+                curve.kernel_point(t.add(F11::new(3)))
+            );
+        }
+    }
+
+    #[test]
+    fn compose__covers_paper_3_2_curve() {
+        type F11 = Fq<11>;
+
+        let a = RingElement::new(F11::new(1), F11::new(2)); // 1+2ε
+        let d = RingElement::new(F11::new(2), F11::new(1)); // 2+ε
+        let curve = TwistedHessianCurve::new(a, d);
+        let residue = curve.reduce();
+        let p = Projective::new(
+            RingElement::from_field(F11::new(1)),
+            RingElement::new(F11::new(7), F11::new(6)),
+            RingElement::new(F11::new(4), F11::new(6)),
+        );
+
+        let decomposition = curve.decomposition();
+        assert_eq!(decomposition.residue_order(), curve.residue_order());
+
+        let (p0, t) = decomposition.decompose(&p);
+        for s in (0..11).map(F11::new) {
+            let composed = decomposition.compose(&p0, s);
+            assert!(curve.contains(&composed));
+            assert_eq!(decomposition.decompose(&composed), (p0, s));
+            assert_eq!(composed == p, s == t);
+        }
+        assert_eq!(residue.point_order(&p0), curve.point_order(&p) / 11);
+    }
+
+    #[test]
+    fn lift_point__reproduces_paper_3_1_point() {
+        let (curve, generator) = paper_curve_and_generator();
+        let residue = generator.reduce();
+
+        // P = [1 : 2 : 3+ε] lies over [1:2:3]
+        let lifts: Vec<_> = curve.all_lifts(&residue).collect();
+        assert_eq!(lifts.len(), 5);
+        let t = lifts.iter().position(|p| *p == generator).unwrap();
+        assert_eq!(curve.lift_point(&residue, Fq::new(t as u64)), generator);
+    }
+
+    #[test]
+    fn all_lifts__are_the_distinct_points_over_the_residue() {
+        type F11 = Fq<11>;
+
+        let a = RingElement::new(F11::new(1), F11::new(2)); // 1+2ε
+        let d = RingElement::new(F11::new(2), F11::new(1)); // 2+ε
+        let curve = TwistedHessianCurve::new(a, d);
+        let residue = curve.reduce();
+        let zero = RingElement::from_field(F11::new(0));
+        let one = RingElement::from_field(F11::new(1));
+
+        // an affine point, the identity and a point at infinity with x = -1
+        let at_infinity = Projective::new(zero.sub(one), one, zero);
+        let p0 = curve
+            .decompose(&Projective::new(
+                one,
+                RingElement::new(F11::new(7), F11::new(6)),
+                RingElement::new(F11::new(4), F11::new(6)),
+            ))
+            .0;
+
+        for point in [p0, Projective::identity(), at_infinity] {
+            assert!(residue.contains(&point));
+            let lifts: Vec<_> = curve.all_lifts(&point).collect();
+
+            for (i, lift) in lifts.iter().enumerate() {
+                assert!(curve.contains(lift));
+                assert_eq!(lift.reduce(), point);
+                assert!(lifts[..i].iter().all(|other| other != lift));
+            }
+            assert_eq!(lifts.len(), 11);
+        }
+    }
+
+    #[test]
+    fn lift_parameter__inverts_lift_point() {
+        let (curve, generator) = paper_curve_and_generator();
+        let unit = RingElement::new(Fq::new(2), Fq::new(3));
+
+        for k in 0..45u64 {
+            let point = curve.scalar_mul(&generator, k);
+            let residue = point.reduce();
+            let t = curve.lift_parameter(&residue, &point).unwrap();
+            assert_eq!(curve.lift_point(&residue, t), point);
+
+            // any representative of the point gives the same parameter
+            let scaled = Projective::new(
+                point.x().mul(unit),
+                point.y().mul(unit),
+                point.z().mul(unit),
+            );
+            assert_eq!(curve.lift_parameter(&residue, &scaled), Some(t));
+        }
+
+        let other = curve.reduce().identity();
+        assert_eq!(curve.lift_parameter(&other, &generator), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Point must be on the residue curve")]
+    fn compose__should_panic_when_point_is_off_residue_curve() {
+        let (curve, _) = paper_curve_and_generator();
+        let one = RingElement::from_field(Fq::new(1));
+
+        curve.compose(&Projective::new(one, one, one), Fq::new(0));
+    }
+}
@@ -4,7 +4,7 @@ use crate::field::Fq;
 use core::ops::Mul;
 
 /// Element in the local ring Fq[ε] where ε² = 0
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct RingElement<const Q: u64> {
     a: Fq<Q>,
     b: Fq<Q>,
@@ -60,6 +60,14 @@ impl<const Q: u64> RingElement<Q> {
         )
     }
 
+    /// Select `a` if `choice` is false and `b` if it is true, without branching
+    pub fn conditional_select(a: &Self, b: &Self, choice: bool) -> Self {
+        RingElement::new(
+            Fq::conditional_select(&a.a, &b.a, choice),
+            Fq::conditional_select(&a.b, &b.b, choice),
+        )
+    }
+
     /// Raise a ring element to a power
     pub fn pow(&self, exponent: u64) -> Self {
         // TODO: optimize using fermat's little theorem
@@ -0,0 +1,335 @@
+//! Fixed-width integers used as scalars for point multiplication
+
+use core::cmp::Ordering;
+
+/// Number of 64-bit limbs in a [`Scalar`]
+pub const LIMBS: usize = 4;
+
+/// Unsigned 256-bit integer used as a scalar for point multiplication
+///
+/// Limbs are stored least significant first. Every `u64` converts losslessly through
+/// [`From`], so small scalars can still be written as plain integers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Scalar {
+    limbs: [u64; LIMBS],
+}
+
+impl Scalar {
+    /// Width of a scalar in bits
+    pub const BITS: u32 = 256;
+
+    /// The scalar 0
+    pub const ZERO: Scalar = Scalar::from_u64(0);
+
+    /// The scalar 1
+    pub const ONE: Scalar = Scalar::from_u64(1);
+
+    /// The largest representable scalar, 2^256 - 1
+    pub const MAX: Scalar = Scalar::from_limbs([u64::MAX; LIMBS]);
+
+    /// Create a scalar from its limbs, least significant first
+    pub const fn from_limbs(limbs: [u64; LIMBS]) -> Self {
+        Scalar { limbs }
+    }
+
+    /// Create a scalar from a `u64`
+    pub const fn from_u64(value: u64) -> Self {
+        Scalar::from_limbs([value, 0, 0, 0])
+    }
+
+    /// Get the limbs, least significant first
+    pub fn limbs(&self) -> [u64; LIMBS] {
+        self.limbs
+    }
+
+    /// Create a scalar from 32 little-endian bytes
+    pub fn from_le_bytes(bytes: &[u8; 32]) -> Self {
+        let mut limbs = [0u64; LIMBS];
+        for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks_exact(8)) {
+            *limb = u64::from_le_bytes(chunk.try_into().expect("chunk of 8 bytes"));
+        }
+        Scalar { limbs }
+    }
+
+    /// Encode the scalar as 32 little-endian bytes
+    pub fn to_le_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for (chunk, limb) in bytes.chunks_exact_mut(8).zip(&self.limbs) {
+            chunk.copy_from_slice(&limb.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Create a scalar from 32 big-endian bytes
+    pub fn from_be_bytes(bytes: &[u8; 32]) -> Self {
+        let mut le = *bytes;
+        le.reverse();
+        Scalar::from_le_bytes(&le)
+    }
+
+    /// Encode the scalar as 32 big-endian bytes
+    pub fn to_be_bytes(&self) -> [u8; 32] {
+        let mut bytes = self.to_le_bytes();
+        bytes.reverse();
+        bytes
+    }
+
+    /// Convert to a `u64` if the scalar fits
+    pub fn to_u64(&self) -> Option<u64> {
+        self.limbs[1..]
+            .iter()
+            .all(|l| *l == 0)
+            .then_some(self.limbs[0])
+    }
+
+    /// Check if the scalar is zero
+    pub fn is_zero(&self) -> bool {
+        self.limbs.iter().all(|l| *l == 0)
+    }
+
+    /// Number of significant bits, 0 for zero
+    pub fn bits(&self) -> u32 {
+        let mut bits = Scalar::BITS;
+        for limb in self.limbs.iter().rev() {
+            if *limb != 0 {
+                return bits.saturating_sub(limb.leading_zeros());
+            }
+            bits = bits.saturating_sub(u64::BITS);
+        }
+        0
+    }
+
+    /// Get bit `i`, counting from the least significant bit
+    pub fn bit(&self, i: u32) -> bool {
+        self.bits_at(i, 1) == 1
+    }
+
+    /// Get `count` (at most 64) bits starting at bit `pos`, reading zeros past the top
+    pub(crate) fn bits_at(&self, pos: u32, count: u32) -> u64 {
+        debug_assert!(count <= u64::BITS);
+
+        let limb = (pos / u64::BITS) as usize;
+        let offset = pos % u64::BITS;
+        let low = self.limbs.get(limb).copied().unwrap_or(0) >> offset;
+        let high = self
+            .limbs
+            .get(limb.saturating_add(1))
+            .copied()
+            .unwrap_or(0)
+            .checked_shl(u64::BITS.saturating_sub(offset))
+            .unwrap_or(0);
+        let mask = 1u64.checked_shl(count).unwrap_or(0).wrapping_sub(1);
+
+        (low | high) & mask
+    }
+
+    /// Addition, `None` on overflow
+    pub fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        let (sum, carry) = self.overflowing_add(rhs);
+        (!carry).then_some(sum)
+    }
+
+    /// Subtraction, `None` on underflow
+    pub fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        let (diff, borrow) = self.overflowing_sub(rhs);
+        (!borrow).then_some(diff)
+    }
+
+    /// Multiplication, `None` on overflow
+    pub fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        let mut wide = [0u64; 2 * LIMBS];
+
+        for (i, a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u128;
+            for (j, b) in rhs.limbs.iter().enumerate() {
+                // a·b + wide + carry < 2^128, so none of these wrap
+                let cell = &mut wide[i.saturating_add(j)];
+                let t = u128::from(*a)
+                    .wrapping_mul(u128::from(*b))
+                    .wrapping_add(u128::from(*cell))
+                    .wrapping_add(carry);
+                let (low, high) = split_u128(t);
+                *cell = low;
+                carry = u128::from(high);
+            }
+            wide[i.saturating_add(LIMBS)] = split_u128(carry).0;
+        }
+
+        if wide[LIMBS..].iter().any(|l| *l != 0) {
+            return None;
+        }
+        Some(Scalar::from_limbs(
+            wide[..LIMBS].try_into().expect("LIMBS limbs"),
+        ))
+    }
+
+    /// Remainder of the division by `modulus`
+    pub fn rem(&self, modulus: &Self) -> Self {
+        assert!(!modulus.is_zero(), "Cannot reduce modulo zero");
+
+        // binary long division, keeping the remainder below the modulus
+        let mut remainder = Scalar::ZERO;
+        for i in (0..self.bits()).rev() {
+            let (shifted, overflow) = remainder.overflowing_shl1();
+            remainder = shifted;
+            remainder.limbs[0] |= u64::from(self.bit(i));
+
+            if overflow || remainder >= *modulus {
+                remainder = remainder.overflowing_sub(modulus).0;
+            }
+        }
+
+        remainder
+    }
+
+    fn overflowing_add(&self, rhs: &Self) -> (Self, bool) {
+        let mut limbs = [0u64; LIMBS];
+        let mut carry = false;
+        for ((out, a), b) in limbs.iter_mut().zip(&self.limbs).zip(&rhs.limbs) {
+            let (sum, c1) = a.overflowing_add(*b);
+            let (sum, c2) = sum.overflowing_add(u64::from(carry));
+            *out = sum;
+            carry = c1 | c2;
+        }
+        (Scalar { limbs }, carry)
+    }
+
+    fn overflowing_sub(&self, rhs: &Self) -> (Self, bool) {
+        let mut limbs = [0u64; LIMBS];
+        let mut borrow = false;
+        for ((out, a), b) in limbs.iter_mut().zip(&self.limbs).zip(&rhs.limbs) {
+            let (diff, b1) = a.overflowing_sub(*b);
+            let (diff, b2) = diff.overflowing_sub(u64::from(borrow));
+            *out = diff;
+            borrow = b1 | b2;
+        }
+        (Scalar { limbs }, borrow)
+    }
+
+    fn overflowing_shl1(&self) -> (Self, bool) {
+        let mut limbs = [0u64; LIMBS];
+        let mut carry = 0u64;
+        for (out, limb) in limbs.iter_mut().zip(&self.limbs) {
+            *out = (limb << 1) | carry;
+            carry = limb >> 63;
+        }
+        (Scalar { limbs }, carry == 1)
+    }
+}
+
+impl From<u64> for Scalar {
+    fn from(value: u64) -> Self {
+        Scalar::from_u64(value)
+    }
+}
+
+impl From<u128> for Scalar {
+    fn from(value: u128) -> Self {
+        let (low, high) = split_u128(value);
+        Scalar::from_limbs([low, high, 0, 0])
+    }
+}
+
+/// Split a `u128` into its low and high 64-bit halves
+fn split_u128(value: u128) -> (u64, u64) {
+    let low = u64::try_from(value & u128::from(u64::MAX)).expect("masked to 64 bits");
+    let high = u64::try_from(value >> 64).expect("shifted to 64 bits");
+    (low, high)
+}
+
+impl Ord for Scalar {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs.iter().rev().cmp(other.limbs.iter().rev())
+    }
+}
+
+impl PartialOrd for Scalar {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::proptest;
+
+    #[test]
+    fn bits__computes_correctly() {
+        assert_eq!(Scalar::ZERO.bits(), 0);
+        assert_eq!(Scalar::ONE.bits(), 1);
+        assert_eq!(Scalar::from(45u64).bits(), 6);
+        assert_eq!(Scalar::from(1u128 << 64).bits(), 65);
+        assert_eq!(Scalar::MAX.bits(), 256);
+    }
+
+    #[test]
+    fn bits_at__reads_across_limbs() {
+        let s = Scalar::from_limbs([0xf000_0000_0000_0000, 0b1011, 0, 0]);
+
+        assert_eq!(s.bits_at(60, 8), 0b1011_1111);
+        assert_eq!(s.bits_at(64, 4), 0b1011);
+        assert_eq!(s.bits_at(250, 10), 0);
+        assert!(s.bit(65));
+        assert!(!s.bit(66));
+    }
+
+    #[test]
+    fn bytes__round_trip() {
+        let s = Scalar::from_limbs([1, 2, 3, 4]);
+
+        assert_eq!(Scalar::from_le_bytes(&s.to_le_bytes()), s);
+        assert_eq!(Scalar::from_be_bytes(&s.to_be_bytes()), s);
+        assert_eq!(s.to_be_bytes()[31], 1);
+        assert_eq!(s.to_le_bytes()[24], 4);
+    }
+
+    #[test]
+    fn checked_ops__detect_overflow() {
+        assert_eq!(Scalar::MAX.checked_add(&Scalar::ONE), None);
+        assert_eq!(Scalar::ZERO.checked_sub(&Scalar::ONE), None);
+        assert_eq!(Scalar::MAX.checked_mul(&Scalar::from(2u64)), None);
+        assert_eq!(
+            Scalar::MAX.checked_sub(&Scalar::MAX.checked_sub(&Scalar::ONE).unwrap()),
+            Some(Scalar::ONE)
+        );
+    }
+
+    #[test]
+    fn ord__compares_most_significant_limb_first() {
+        assert!(Scalar::from_limbs([0, 1, 0, 0]) > Scalar::from(u64::MAX));
+        assert!(Scalar::from(3u64) < Scalar::from(4u64));
+    }
+
+    #[test]
+    fn rem__reduces_near_the_top_of_the_range() {
+        let modulus = Scalar::MAX.checked_sub(&Scalar::from(4u64)).unwrap();
+        assert_eq!(Scalar::MAX.rem(&modulus), Scalar::from(4u64));
+        assert_eq!(Scalar::MAX.rem(&Scalar::MAX), Scalar::ZERO);
+    }
+
+    #[test]
+    fn arithmetic__matches_u128_proptest() {
+        proptest!(|(a: u128, b: u128, m in 1u128..)| {
+            let (sa, sb, sm) = (Scalar::from(a), Scalar::from(b), Scalar::from(m));
+
+            assert_eq!(sa.rem(&sm), Scalar::from(a.checked_rem(m).unwrap()));
+            assert_eq!(sa.cmp(&sb), a.cmp(&b));
+
+            let (sum, carry) = a.overflowing_add(b);
+            let (low, high) = split_u128(sum);
+            assert_eq!(
+                sa.checked_add(&sb),
+                Some(Scalar::from_limbs([low, high, u64::from(carry), 0]))
+            );
+            assert_eq!(sa.checked_sub(&sb), a.checked_sub(b).map(Scalar::from));
+
+            let (lo_a, lo_b) = (split_u128(a).0, split_u128(b).0);
+            assert_eq!(
+                Scalar::from(lo_a).checked_mul(&Scalar::from(lo_b)),
+                Some(Scalar::from(u128::from(lo_a).wrapping_mul(u128::from(lo_b))))
+            );
+        });
+    }
+}
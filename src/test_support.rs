@@ -0,0 +1,50 @@
+//! Fixtures shared by the unit tests
+
+use crate::{
+    curve::TwistedHessianCurve,
+    field::Fq,
+    projective::Projective,
+    ring::RingElement,
+};
+
+type F5 = Fq<5>;
+
+/// The curve a = d = 1+ε over F5[ε] from Section 3.1 of the paper
+pub(crate) fn paper_curve() -> TwistedHessianCurve<5> {
+    let a = RingElement::new(F5::new(1), F5::new(1)); // 1+ε
+    let d = RingElement::new(F5::new(1), F5::new(1)); // 1+ε
+
+    TwistedHessianCurve::new(a, d)
+}
+
+/// The paper curve with its point P = [1:2:3+ε] of order 45, which generates all 45
+/// points
+pub(crate) fn paper_curve_and_generator() -> (TwistedHessianCurve<5>, Projective<5>) {
+    let generator = Projective::new(
+        RingElement::from_field(F5::new(1)),
+        RingElement::from_field(F5::new(2)),
+        RingElement::new(F5::new(3), F5::new(1)),
+    );
+
+    (paper_curve(), generator)
+}
+
+/// Every point of a curve, as the q lifts of each point of the residue curve
+pub(crate) fn all_points<const Q: u64>(
+    curve: &TwistedHessianCurve<Q>,
+) -> Vec<Projective<Q>> {
+    let residue = curve.reduce();
+    let one = RingElement::from_field(Fq::new(1));
+    let field = |v| RingElement::from_field(Fq::new(v));
+
+    // [x:y:1] and [x:1:0], as [x:0:0] is never on the curve
+    let finite = (0..Q)
+        .flat_map(|x| (0..Q).map(move |y| Projective::new(field(x), field(y), one)));
+    let at_infinity = (0..Q).map(|x| Projective::new(field(x), one, field(0)));
+
+    finite
+        .chain(at_infinity)
+        .filter(|p| residue.contains(p))
+        .flat_map(|p| (0..Q).map(move |t| curve.lift_point(&p, Fq::new(t))))
+        .collect()
+}
@@ -0,0 +1,185 @@
+//! The 3-torsion subgroup of a twisted Hessian curve
+//!
+//! The points of order dividing 3 are the nine inflection points, where the curve meets
+//! XYZ = 0. With ω a primitive cube root of unity and β³ = a, they are [0 : -ζ : 1]
+//! for ζ³ = 1, and [-1/β : 1 : 0] and [-1/β : 0 : 1] for the three choices of β.
+//! Translation by each of them is a scaling and cyclic permutation of coordinates:
+//!
+//! - P + [0 : -ζ : 1] = [X : ζ²Y : ζZ]
+//! - P + [-1/β : 1 : 0] = [Y/β : Z : βX]
+//! - P + [-1/β : 0 : 1] = [Z/β : βX : Y]
+//!
+//! Since E(Fq[ε]) ≅ E(Fq) × Fq and 3 ∤ q, the 3-torsion of the curve over Fq[ε] is
+//! that of the residue curve, each point lifted through the unique lifts of ζ and β.
+
+use crate::{
+    curve::TwistedHessianCurve,
+    field::Fq,
+    projective::Projective,
+    ring::RingElement,
+};
+use core::ops::{
+    Mul,
+    Sub,
+};
+
+impl<const Q: u64> TwistedHessianCurve<Q> {
+    /// Get the cube roots β of a in Fq[ε], lifted from the cube roots of a₀ in Fq
+    ///
+    /// There are three when q ≡ 1 mod 3 and a₀ is a cube, one when q ≡ 2 mod 3, and
+    /// none otherwise.
+    pub fn cube_roots_of_a(&self) -> impl Iterator<Item = RingElement<Q>> {
+        assert!(Q != 3, "Hessian 3-torsion needs q ≠ 3");
+
+        let a = self.a();
+        let omega = Fq::<Q>::cube_root_of_unity();
+        let root = a.constant().cube_root();
+
+        let roots = [
+            root,
+            root.zip(omega).map(|(r, w)| r.mul(w)),
+            root.zip(omega).map(|(r, w)| r.mul(w).mul(w)),
+        ];
+
+        // (β₀ + β₁ε)³ = β₀³ + 3β₀²β₁ε
+        roots.into_iter().flatten().map(move |beta0| {
+            let beta1 = a
+                .epsilon_coeff()
+                .mul(Fq::new(3).mul(beta0).mul(beta0).inv());
+            RingElement::new(beta0, beta1)
+        })
+    }
+
+    /// Get the points of order dividing 3 with coordinates in Fq[ε], the identity
+    /// first
+    ///
+    /// All nine are rational when q ≡ 1 mod 3 and a₀ is a cube, and three otherwise.
+    pub fn three_torsion_points(&self) -> impl Iterator<Item = Projective<Q>> {
+        let zero = RingElement::from_field(Fq::new(0));
+        let one = RingElement::from_field(Fq::new(1));
+
+        // [0 : -ζ : 1] for the cube roots of unity ζ = 1, ω, ω²
+        let unity = Fq::<Q>::cube_root_of_unity()
+            .into_iter()
+            .flat_map(move |omega| {
+                [omega, omega.mul(omega)].map(|zeta| {
+                    Projective::new(zero, zero.sub(RingElement::from_field(zeta)), one)
+                })
+            });
+
+        // [-1/β : 1 : 0] and [-1/β : 0 : 1]
+        let inflection = self.cube_roots_of_a().flat_map(move |beta| {
+            let x = zero.sub(beta.inv());
+            [Projective::new(x, one, zero), Projective::new(x, zero, one)]
+        });
+
+        core::iter::once(Projective::identity())
+            .chain(unity)
+            .chain(inflection)
+    }
+
+    /// Translate a point by a point of order dividing 3, which only scales and permutes
+    /// coordinates
+    pub fn translate(
+        &self,
+        point: &Projective<Q>,
+        torsion: &Projective<Q>,
+    ) -> Projective<Q> {
+        assert!(self.contains(point), "Point must be on the curve");
+        assert!(self.contains(torsion), "Torsion point must be on the curve");
+
+        let zero = RingElement::from_field(Fq::new(0));
+        let (x, y, z) = (point.x(), point.y(), point.z());
+        let (tx, ty, tz) = (torsion.x(), torsion.y(), torsion.z());
+
+        if tx == zero {
+            // [0 : -ζ : 1] ↦ [X : ζ²Y : ζZ]
+            let zeta = zero.sub(ty.mul(tz.inv()));
+            Projective::new(x, zeta.mul(zeta).mul(y), zeta.mul(z))
+        } else if tz == zero {
+            // [-1/β : 1 : 0] ↦ [Y/β : Z : βX]
+            let beta = zero.sub(ty.mul(tx.inv()));
+            Projective::new(y.mul(beta.inv()), z, beta.mul(x))
+        } else {
+            // [-1/β : 0 : 1] ↦ [Z/β : βX : Y]
+            assert!(ty == zero, "Torsion point must have order dividing 3");
+            let beta = zero.sub(tz.mul(tx.inv()));
+            Projective::new(z.mul(beta.inv()), beta.mul(x), y)
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{
+        all_points,
+        paper_curve_and_generator,
+    };
+
+    #[test]
+    fn three_torsion_points__are_all_nine_when_rational() {
+        type F7 = Fq<7>;
+
+        // 1 is a cube and ω = 2 exists in F7
+        let a = RingElement::new(F7::new(1), F7::new(1)); // 1+ε
+        let d = RingElement::new(F7::new(2), F7::new(1)); // 2+ε
+        let curve = TwistedHessianCurve::new(a, d);
+        let torsion: Vec<_> = curve.three_torsion_points().collect();
+
+        assert_eq!(torsion.len(), 9);
+        assert!(torsion[0].is_identity());
+        for (i, t) in torsion.iter().enumerate() {
+            assert!(curve.contains(t));
+            assert!(matches!(curve.point_order(t), 1 | 3));
+            assert!(torsion[..i].iter().all(|other| other != t));
+        }
+
+        // the same points over Fq, reduced modulo ε
+        let residue = curve.reduce();
+        for (t, t0) in torsion.iter().zip(residue.three_torsion_points()) {
+            assert_eq!(t.reduce(), t0);
+            assert!(matches!(residue.point_order(&t0), 1 | 3));
+        }
+    }
+
+    #[test]
+    fn three_torsion_points__kats_paper_3_1() {
+        // q ≡ 2 mod 3, so only the identity and the two points above a = β³ are rational
+        let (curve, p) = paper_curve_and_generator();
+
+        // P has order 45, so the points of order 3 are 15P and 30P
+        let torsion: Vec<_> = curve.three_torsion_points().collect();
+        assert_eq!(torsion.len(), 3);
+        assert!(torsion.contains(&curve.scalar_mul(&p, 15u64)));
+        assert!(torsion.contains(&curve.scalar_mul(&p, 30u64)));
+        for t in &torsion {
+            assert!(matches!(curve.point_order(t), 1 | 3));
+        }
+    }
+
+    #[test]
+    fn translate__matches_addition_over_all_points() {
+        type F7 = Fq<7>;
+
+        let a = RingElement::new(F7::new(1), F7::new(1)); // 1+ε
+        let d = RingElement::new(F7::new(2), F7::new(1)); // 2+ε
+        let curve = TwistedHessianCurve::new(a, d);
+        let points = all_points(&curve);
+
+        for t in curve.three_torsion_points() {
+            for p in &points {
+                assert_eq!(curve.translate(p, &t), curve.add(p, &t));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Torsion point must have order dividing 3")]
+    fn translate__should_panic_when_point_is_not_3_torsion() {
+        let (curve, p) = paper_curve_and_generator();
+
+        curve.translate(&p, &p);
+    }
+}
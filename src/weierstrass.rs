@@ -0,0 +1,857 @@
+//! Short Weierstrass curves over Fq[ε] and the maps to and from twisted Hessian curves
+//!
+//! For q > 3 the linear change of coordinates
+//!
+//! (X : Y : Z) ↦ (c₃X - kT : c₃(Y - Z) : T), T = dX + 3(Y + Z),
+//!
+//! with c₃ = 4(d³ - 27a)/27 and k = d²/9, sends aX³ + Y³ + Z³ = dXYZ onto
+//! y² = x³ + Ax + B with A = -d(216a + d³)/243 and B = -2(5832a² + 540ad³ - d⁶)/19683.
+//! T = 0 is the tangent line at the identity [0:-1:1], which therefore goes to the
+//! point at infinity, and as the map is linear and invertible whenever the Hessian
+//! curve is valid, it is a group isomorphism without exceptional points.
+//!
+//! Montgomery and twisted Edwards models exist when the Weierstrass curve has a point
+//! (α, 0) of order 2 with 3α² + A a square, see [`crate::edwards`].
+
+#[cfg(feature = "alloc")]
+use crate::poly::Polynomial;
+use crate::{
+    curve::TwistedHessianCurve,
+    field::Fq,
+    projective::Projective,
+    ring::RingElement,
+    scalar::Scalar,
+};
+#[cfg(feature = "alloc")]
+use alloc::vec;
+use core::ops::{
+    Add,
+    Mul,
+    Sub,
+};
+
+/// Represents a short Weierstrass curve y² = x³ + Ax + B over the ring Fq[ε]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeierstrassCurve<const Q: u64> {
+    a: RingElement<Q>,
+    b: RingElement<Q>,
+}
+
+impl<const Q: u64> WeierstrassCurve<Q> {
+    /// Create a new short Weierstrass curve with coefficients A and B
+    pub fn new(a: RingElement<Q>, b: RingElement<Q>) -> Self {
+        assert!(Q > 3, "Short Weierstrass curves need q > 3");
+
+        let discriminant = constant(4)
+            .mul(a)
+            .mul(a)
+            .mul(a)
+            .add(constant(27).mul(b).mul(b));
+        assert!(
+            discriminant.is_invertible(),
+            "4A³ + 27B² must be invertible for a valid curve"
+        );
+
+        WeierstrassCurve { a, b }
+    }
+
+    /// Get the A coefficient of the curve
+    pub fn a(&self) -> RingElement<Q> {
+        self.a
+    }
+
+    /// Get the B coefficient of the curve
+    pub fn b(&self) -> RingElement<Q> {
+        self.b
+    }
+
+    /// Get the identity element of the curve group
+    pub fn identity(&self) -> WeierstrassPoint<Q> {
+        WeierstrassPoint::identity()
+    }
+
+    /// Check if a point lies on this curve
+    pub fn contains(&self, point: &WeierstrassPoint<Q>) -> bool {
+        point.is_on_curve(self)
+    }
+
+    /// Add two points on this curve
+    pub fn add(
+        &self,
+        p: &WeierstrassPoint<Q>,
+        q: &WeierstrassPoint<Q>,
+    ) -> WeierstrassPoint<Q> {
+        assert!(self.contains(p), "Point P must be on the curve");
+        assert!(self.contains(q), "Point Q must be on the curve");
+
+        p.add(q, self)
+    }
+
+    /// Multiply a point by a scalar
+    pub fn scalar_mul(
+        &self,
+        p: &WeierstrassPoint<Q>,
+        scalar: impl Into<Scalar>,
+    ) -> WeierstrassPoint<Q> {
+        assert!(self.contains(p), "Point must be on the curve");
+
+        p.scalar_mul(scalar, self)
+    }
+}
+
+/// Represents a point [X:Y:Z] on a short Weierstrass curve in homogeneous projective
+/// coordinates, i.e. on Y²Z = X³ + AXZ² + BZ³
+///
+/// `==` compares points up to scaling by a unit of Fq[ε].
+#[derive(Debug, Clone, Copy)]
+pub struct WeierstrassPoint<const Q: u64> {
+    x: RingElement<Q>,
+    y: RingElement<Q>,
+    z: RingElement<Q>,
+}
+
+impl<const Q: u64> WeierstrassPoint<Q> {
+    /// Create a new projective point [X:Y:Z]
+    pub const fn new(x: RingElement<Q>, y: RingElement<Q>, z: RingElement<Q>) -> Self {
+        WeierstrassPoint { x, y, z }
+    }
+
+    /// Create the affine point (x, y) = [x:y:1]
+    pub fn from_affine(x: RingElement<Q>, y: RingElement<Q>) -> Self {
+        WeierstrassPoint::new(x, y, constant(1))
+    }
+
+    /// Create the identity element, the point at infinity [0:1:0]
+    pub fn identity() -> Self {
+        WeierstrassPoint::new(constant(0), constant(1), constant(0))
+    }
+
+    /// Get the x-coordinate
+    pub fn x(&self) -> RingElement<Q> {
+        self.x
+    }
+
+    /// Get the y-coordinate
+    pub fn y(&self) -> RingElement<Q> {
+        self.y
+    }
+
+    /// Get the z-coordinate
+    pub fn z(&self) -> RingElement<Q> {
+        self.z
+    }
+
+    /// Check if this is the identity point, i.e. [0:u:0] for some unit u
+    pub fn is_identity(&self) -> bool {
+        self.x == constant(0) && self.z == constant(0) && self.y.is_invertible()
+    }
+
+    /// Check if a point is "projectively equal" to another
+    pub fn is_equal(&self, other: &Self) -> bool {
+        self.x.mul(other.y) == other.x.mul(self.y)
+            && self.x.mul(other.z) == other.x.mul(self.z)
+            && self.y.mul(other.z) == other.y.mul(self.z)
+    }
+
+    /// Check if a point lies on the curve Y²Z = X³ + AXZ² + BZ³
+    pub fn is_on_curve(&self, curve: &WeierstrassCurve<Q>) -> bool {
+        let z_squared = self.z.mul(self.z);
+
+        let lhs = self.y.mul(self.y).mul(self.z);
+        let rhs = self
+            .x
+            .mul(self.x)
+            .mul(self.x)
+            .add(curve.a.mul(self.x).mul(z_squared))
+            .add(curve.b.mul(z_squared).mul(self.z));
+
+        self.has_unit_coordinate() && lhs == rhs
+    }
+
+    /// Get the affine coordinates (X/Z, Y/Z), or `None` if Z is not invertible
+    ///
+    /// Over Fq[ε] this excludes the identity and every point reducing to it modulo ε.
+    pub fn to_affine(&self) -> Option<(RingElement<Q>, RingElement<Q>)> {
+        if !self.z.is_invertible() {
+            return None;
+        }
+
+        let z_inv = self.z.inv();
+        Some((self.x.mul(z_inv), self.y.mul(z_inv)))
+    }
+
+    /// Convert to Jacobian coordinates
+    pub fn to_jacobian(&self) -> JacobianPoint<Q> {
+        if self.z.is_invertible() {
+            // x = XZ/Z², y = YZ²/Z³
+            let z_squared = self.z.mul(self.z);
+            JacobianPoint::new(self.x.mul(self.z), self.y.mul(z_squared), self.z)
+        } else {
+            // a point [εt:1:0] reducing to the identity is (1:1:εt), see
+            // [`JacobianPoint::to_projective`]
+            assert!(
+                self.y.is_invertible(),
+                "Point must have an invertible Y or Z coordinate"
+            );
+            JacobianPoint::new(constant(1), constant(1), self.x.mul(self.y.inv()))
+        }
+    }
+
+    /// Negate a point: -[X:Y:Z] = [X:-Y:Z]
+    pub fn negate(&self) -> Self {
+        WeierstrassPoint::new(self.x, constant(0).sub(self.y), self.z)
+    }
+
+    /// Add two points on a short Weierstrass curve
+    ///
+    /// Uses the two addition laws of bidegree (2, 2) of Bosma and Lenstra attached to
+    /// the lines Z = 0 and Y = 0. The first is exceptional only when P₁ = P₂ and the
+    /// second only when P₁ - P₂ has order 2, so over Fq one of them is always
+    /// non-zero; the second result is selected, without branching, whenever the first
+    /// one vanishes modulo ε.
+    pub fn add(&self, other: &Self, curve: &WeierstrassCurve<Q>) -> Self {
+        let z_law = self.add_z_law(other, curve);
+        let y_law = self.add_y_law(other, curve);
+
+        WeierstrassPoint::conditional_select(&z_law, &y_law, !z_law.has_unit_coordinate())
+    }
+
+    /// Addition law attached to the line Z = 0, exceptional when P₁ = P₂
+    fn add_z_law(&self, other: &Self, curve: &WeierstrassCurve<Q>) -> Self {
+        let (x1, y1, z1) = (self.x, self.y, self.z);
+        let (x2, y2, z2) = (other.x, other.y, other.z);
+        let (a, b) = (curve.a, curve.b);
+        let three = constant(3);
+
+        let x1z2 = x1.mul(z2);
+        let z1x2 = z1.mul(x2);
+        let y1z2 = y1.mul(z2);
+        let z1y2 = z1.mul(y2);
+        let y1x2 = y1.mul(x2);
+        let x1y2 = x1.mul(y2);
+        let z1z2 = z1.mul(z2);
+        let three_x1x2 = three.mul(x1).mul(x2);
+
+        // X₃ = X₁Z₁Y₂² - Y₁²X₂Z₂ + 2Y₁Y₂(X₁Z₂ - Z₁X₂) + a(Z₁²X₂² - X₁²Z₂²)
+        //      + 3bZ₁Z₂(Z₁X₂ - X₁Z₂)
+        let x3 = x1
+            .mul(z1)
+            .mul(y2)
+            .mul(y2)
+            .sub(y1.mul(y1).mul(x2).mul(z2))
+            .add(constant(2).mul(y1).mul(y2).mul(x1z2.sub(z1x2)))
+            .add(a.mul(z1x2.mul(z1x2).sub(x1z2.mul(x1z2))))
+            .add(three.mul(b).mul(z1z2).mul(z1x2.sub(x1z2)));
+
+        // Y₃ = Y₁Z₁Y₂² - Y₁²Y₂Z₂ + 3X₁X₂(Y₁X₂ - X₁Y₂) + 3bZ₁Z₂(Y₁Z₂ - Z₁Y₂)
+        //      + a(X₁Y₁Z₂² - Z₁²X₂Y₂ + 2Z₁Z₂(Y₁X₂ - X₁Y₂))
+        let y3 = y1
+            .mul(z1)
+            .mul(y2)
+            .mul(y2)
+            .sub(y1.mul(y1).mul(y2).mul(z2))
+            .add(three_x1x2.mul(y1x2.sub(x1y2)))
+            .add(three.mul(b).mul(z1z2).mul(y1z2.sub(z1y2)))
+            .add(
+                a.mul(
+                    x1.mul(y1z2)
+                        .mul(z2)
+                        .sub(z1x2.mul(z1y2))
+                        .add(constant(2).mul(z1z2).mul(y1x2.sub(x1y2))),
+                ),
+            );
+
+        // Z₃ = Z₁²Y₂² - Y₁²Z₂² + 3X₁X₂(X₁Z₂ - Z₁X₂) + aZ₁Z₂(X₁Z₂ - Z₁X₂)
+        let z3 = z1y2
+            .mul(z1y2)
+            .sub(y1z2.mul(y1z2))
+            .add(three_x1x2.mul(x1z2.sub(z1x2)))
+            .add(a.mul(z1z2).mul(x1z2.sub(z1x2)));
+
+        WeierstrassPoint::new(x3, y3, z3)
+    }
+
+    /// Addition law attached to the line Y = 0, exceptional when P₁ - P₂ has order 2
+    fn add_y_law(&self, other: &Self, curve: &WeierstrassCurve<Q>) -> Self {
+        let (x1, y1, z1) = (self.x, self.y, self.z);
+        let (x2, y2, z2) = (other.x, other.y, other.z);
+        let (a, b) = (curve.a, curve.b);
+        let a_squared = a.mul(a);
+
+        let x1x2 = x1.mul(x2);
+        let y1y2 = y1.mul(y2);
+        let z1z2 = z1.mul(z2);
+        let z1x2 = z1.mul(x2);
+        let x1z2 = x1.mul(z2);
+        // symmetric sums X₁Y₂ + Y₁X₂, Z₁Y₂ + Y₁Z₂ and Z₁X₂ + X₁Z₂
+        let xy = x1.mul(y2).add(y1.mul(x2));
+        let zy = z1.mul(y2).add(y1.mul(z2));
+        let zx = z1x2.add(x1z2);
+        // Z₁²X₂Y₂ + X₁Y₁Z₂²
+        let zzxy = z1.mul(z1).mul(x2).mul(y2).add(x1.mul(y1).mul(z2).mul(z2));
+
+        // X₃ = Y₁Y₂(X₁Y₂ + Y₁X₂) + a²Z₁Z₂(Z₁Y₂ + Y₁Z₂) - 3b(Z₁²X₂Y₂ + X₁Y₁Z₂²)
+        //      - 6bZ₁Z₂(Y₁X₂ + X₁Y₂) - a(Y₁Z₁X₂² + X₁²Y₂Z₂) - 2aX₁X₂(Z₁Y₂ + Y₁Z₂)
+        let x3 = y1y2
+            .mul(xy)
+            .add(a_squared.mul(z1z2).mul(zy))
+            .sub(constant(3).mul(b).mul(zzxy))
+            .sub(constant(6).mul(b).mul(z1z2).mul(xy))
+            .sub(a.mul(y1.mul(z1x2).mul(x2).add(x1.mul(x1).mul(y2).mul(z2))))
+            .sub(constant(2).mul(a).mul(x1x2).mul(zy));
+
+        // Y₃ = Y₁²Y₂² + 3aX₁²X₂² - (a³ + 9b²)Z₁²Z₂² - a²(Z₁²X₂² + X₁²Z₂²)
+        //      - 4a²X₁X₂Z₁Z₂ + 9bX₁X₂(Z₁X₂ + X₁Z₂) - 3abZ₁Z₂(Z₁X₂ + X₁Z₂)
+        let y3 = y1y2
+            .mul(y1y2)
+            .add(constant(3).mul(a).mul(x1x2).mul(x1x2))
+            .sub(
+                a_squared
+                    .mul(a)
+                    .add(constant(9).mul(b).mul(b))
+                    .mul(z1z2)
+                    .mul(z1z2),
+            )
+            .sub(a_squared.mul(z1x2.mul(z1x2).add(x1z2.mul(x1z2))))
+            .sub(constant(4).mul(a_squared).mul(x1x2).mul(z1z2))
+            .add(constant(9).mul(b).mul(x1x2).mul(zx))
+            .sub(constant(3).mul(a).mul(b).mul(z1z2).mul(zx));
+
+        // Z₃ = Y₁Y₂(Z₁Y₂ + Y₁Z₂) + 3X₁X₂(Y₁X₂ + X₁Y₂) + a(Z₁²X₂Y₂ + X₁Y₁Z₂²)
+        //      + 2aZ₁Z₂(Y₁X₂ + X₁Y₂) + 3bZ₁Z₂(Z₁Y₂ + Y₁Z₂)
+        let z3 = y1y2
+            .mul(zy)
+            .add(constant(3).mul(x1x2).mul(xy))
+            .add(a.mul(zzxy))
+            .add(constant(2).mul(a).mul(z1z2).mul(xy))
+            .add(constant(3).mul(b).mul(z1z2).mul(zy));
+
+        WeierstrassPoint::new(x3, y3, z3)
+    }
+
+    /// Check if at least one coordinate is invertible, i.e. [X:Y:Z] is a valid point
+    /// over Fq[ε] rather than zero or a multiple of ε
+    fn has_unit_coordinate(&self) -> bool {
+        (self.x.constant().value()
+            | self.y.constant().value()
+            | self.z.constant().value())
+            != 0
+    }
+
+    /// Select `a` if `choice` is false and `b` if it is true, without branching
+    pub fn conditional_select(a: &Self, b: &Self, choice: bool) -> Self {
+        WeierstrassPoint::new(
+            RingElement::conditional_select(&a.x, &b.x, choice),
+            RingElement::conditional_select(&a.y, &b.y, choice),
+            RingElement::conditional_select(&a.z, &b.z, choice),
+        )
+    }
+
+    /// Double a point on a short Weierstrass curve
+    ///
+    /// This is the law attached to Y = 0 with P₁ = P₂, which has no exceptional
+    /// points when doubling.
+    pub fn double(&self, curve: &WeierstrassCurve<Q>) -> Self {
+        self.add_y_law(self, curve)
+    }
+
+    /// Multiply a point by a scalar using double-and-add algorithm
+    pub fn scalar_mul(
+        &self,
+        scalar: impl Into<Scalar>,
+        curve: &WeierstrassCurve<Q>,
+    ) -> Self {
+        let scalar = scalar.into();
+        let mut result = WeierstrassPoint::identity();
+        let mut temp = *self;
+
+        for i in 0..scalar.bits() {
+            if scalar.bit(i) {
+                result = result.add(&temp, curve);
+            }
+            temp = temp.double(curve);
+        }
+
+        result
+    }
+}
+
+impl<const Q: u64> PartialEq for WeierstrassPoint<Q> {
+    fn eq(&self, other: &Self) -> bool {
+        self.is_equal(other)
+    }
+}
+
+impl<const Q: u64> Eq for WeierstrassPoint<Q> {}
+
+/// Represents a point (X:Y:Z) on a short Weierstrass curve in Jacobian coordinates,
+/// x = X/Z² and y = Y/Z³
+///
+/// Points reducing to the identity modulo ε are (1:1:εt), the identity itself being
+/// (1:1:0). Addition and doubling use the usual Jacobian formulas and fall back to the
+/// complete law of [`WeierstrassPoint`] whenever those vanish modulo ε, so no inputs
+/// are exceptional. `==` compares the underlying points.
+#[derive(Debug, Clone, Copy)]
+pub struct JacobianPoint<const Q: u64> {
+    x: RingElement<Q>,
+    y: RingElement<Q>,
+    z: RingElement<Q>,
+}
+
+impl<const Q: u64> JacobianPoint<Q> {
+    /// Create a new point (X:Y:Z) in Jacobian coordinates
+    pub const fn new(x: RingElement<Q>, y: RingElement<Q>, z: RingElement<Q>) -> Self {
+        JacobianPoint { x, y, z }
+    }
+
+    /// Create the identity element (1:1:0)
+    pub fn identity() -> Self {
+        JacobianPoint::new(constant(1), constant(1), constant(0))
+    }
+
+    /// Get the x-coordinate
+    pub fn x(&self) -> RingElement<Q> {
+        self.x
+    }
+
+    /// Get the y-coordinate
+    pub fn y(&self) -> RingElement<Q> {
+        self.y
+    }
+
+    /// Get the z-coordinate
+    pub fn z(&self) -> RingElement<Q> {
+        self.z
+    }
+
+    /// Check if this is the identity point
+    pub fn is_identity(&self) -> bool {
+        self.to_projective().is_identity()
+    }
+
+    /// Convert to homogeneous projective coordinates, (X:Y:Z) ↦ [XZ:Y:Z³]
+    pub fn to_projective(&self) -> WeierstrassPoint<Q> {
+        WeierstrassPoint::new(self.x.mul(self.z), self.y, self.z.mul(self.z).mul(self.z))
+    }
+
+    /// Negate a point: -(X:Y:Z) = (X:-Y:Z)
+    pub fn negate(&self) -> Self {
+        JacobianPoint::new(self.x, constant(0).sub(self.y), self.z)
+    }
+
+    /// Double a point on a short Weierstrass curve
+    pub fn double(&self, curve: &WeierstrassCurve<Q>) -> Self {
+        let (x, y, z) = (self.x, self.y, self.z);
+        let y_squared = y.mul(y);
+        let z_squared = z.mul(z);
+
+        // S = 4XY², M = 3X² + aZ⁴
+        let s = constant(4).mul(x).mul(y_squared);
+        let m = constant(3)
+            .mul(x)
+            .mul(x)
+            .add(curve.a.mul(z_squared).mul(z_squared));
+
+        // X₃ = M² - 2S, Y₃ = M(S - X₃) - 8Y⁴, Z₃ = 2YZ
+        let x3 = m.mul(m).sub(constant(2).mul(s));
+        let y3 = m
+            .mul(s.sub(x3))
+            .sub(constant(8).mul(y_squared).mul(y_squared));
+        let z3 = constant(2).mul(y).mul(z);
+
+        JacobianPoint::new(x3, y3, z3).or_else(|| self.to_projective().double(curve))
+    }
+
+    /// Add two points on a short Weierstrass curve
+    pub fn add(&self, other: &Self, curve: &WeierstrassCurve<Q>) -> Self {
+        let z1_squared = self.z.mul(self.z);
+        let z2_squared = other.z.mul(other.z);
+
+        // U₁ = X₁Z₂², U₂ = X₂Z₁², S₁ = Y₁Z₂³, S₂ = Y₂Z₁³
+        let u1 = self.x.mul(z2_squared);
+        let u2 = other.x.mul(z1_squared);
+        let s1 = self.y.mul(z2_squared).mul(other.z);
+        let s2 = other.y.mul(z1_squared).mul(self.z);
+
+        // H = U₂ - U₁, r = S₂ - S₁
+        let h = u2.sub(u1);
+        let r = s2.sub(s1);
+        let h_squared = h.mul(h);
+        let h_cubed = h_squared.mul(h);
+
+        // X₃ = r² - H³ - 2U₁H², Y₃ = r(U₁H² - X₃) - S₁H³, Z₃ = Z₁Z₂H
+        let x3 = r
+            .mul(r)
+            .sub(h_cubed)
+            .sub(constant(2).mul(u1).mul(h_squared));
+        let y3 = r.mul(u1.mul(h_squared).sub(x3)).sub(s1.mul(h_cubed));
+        let z3 = self.z.mul(other.z).mul(h);
+
+        JacobianPoint::new(x3, y3, z3)
+            .or_else(|| self.to_projective().add(&other.to_projective(), curve))
+    }
+
+    /// Keep a result of the Jacobian formulas if it is a valid point, and otherwise
+    /// recompute it with the complete projective law
+    fn or_else(self, fallback: impl FnOnce() -> WeierstrassPoint<Q>) -> Self {
+        let valid = (self.x.constant().value()
+            | self.y.constant().value()
+            | self.z.constant().value())
+            != 0;
+
+        if valid {
+            self
+        } else {
+            fallback().to_jacobian()
+        }
+    }
+
+    /// Multiply a point by a scalar using double-and-add algorithm
+    pub fn scalar_mul(
+        &self,
+        scalar: impl Into<Scalar>,
+        curve: &WeierstrassCurve<Q>,
+    ) -> Self {
+        let scalar = scalar.into();
+        let mut result = JacobianPoint::identity();
+        let mut temp = *self;
+
+        for i in 0..scalar.bits() {
+            if scalar.bit(i) {
+                result = result.add(&temp, curve);
+            }
+            temp = temp.double(curve);
+        }
+
+        result
+    }
+}
+
+impl<const Q: u64> PartialEq for JacobianPoint<Q> {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_projective() == other.to_projective()
+    }
+}
+
+impl<const Q: u64> Eq for JacobianPoint<Q> {}
+
+impl<const Q: u64> TwistedHessianCurve<Q> {
+    /// Get the isomorphic short Weierstrass curve, see the [`weierstrass`](crate::weierstrass) module
+    pub fn to_weierstrass(&self) -> WeierstrassCurve<Q> {
+        assert!(Q > 3, "Short Weierstrass curves need q > 3");
+
+        let (a, d) = (self.a(), self.d());
+        let d_cubed = d.mul(d).mul(d);
+
+        // A = -d(216a + d³)/243
+        let big_a = constant(0)
+            .sub(d.mul(constant(216).mul(a).add(d_cubed)))
+            .mul(constant(243).inv());
+        // B = -2(5832a² + 540ad³ - d⁶)/19683
+        let big_b = constant(0)
+            .sub(
+                constant(2).mul(
+                    constant(5832)
+                        .mul(a)
+                        .mul(a)
+                        .add(constant(540).mul(a).mul(d_cubed))
+                        .sub(d_cubed.mul(d_cubed)),
+                ),
+            )
+            .mul(constant(19683).inv());
+
+        WeierstrassCurve::new(big_a, big_b)
+    }
+
+    /// Find a twisted Hessian curve isomorphic to a short Weierstrass curve through
+    /// [`TwistedHessianCurve::to_weierstrass`]
+    ///
+    /// Such a curve has d² = -9x₀ for a root x₀ of the 3-division polynomial
+    /// 3x⁴ + 6Ax² + 12Bx - A², i.e. for the x-coordinate of a point of order 3, and
+    /// a = -(243A/d + d³)/216. Returns `None` unless some root is simple modulo ε, so
+    /// it lifts uniquely to Fq[ε], and gives a d with d² a non-zero square.
+    #[cfg(feature = "alloc")]
+    pub fn from_weierstrass(curve: &WeierstrassCurve<Q>) -> Option<Self> {
+        let (big_a, big_b) = (curve.a(), curve.b());
+        let (a0, b0) = (big_a.constant(), big_b.constant());
+        let zero = Fq::new(0);
+
+        let division = Polynomial::new(vec![
+            zero.sub(a0.mul(a0)),
+            Fq::new(12).mul(b0),
+            Fq::new(6).mul(a0),
+            zero,
+            Fq::new(3),
+        ]);
+
+        for x0 in division.roots() {
+            let derivative = division.derivative().evaluate(x0);
+            if derivative.value() == 0 {
+                continue;
+            }
+
+            // the division polynomial at x₀ over Fq[ε] is a multiple of ε, see
+            // [`Projective::from_bytes`] for the same Hensel step
+            let x = RingElement::from_field(x0);
+            let value = constant(3)
+                .mul(x.pow(4))
+                .add(constant(6).mul(big_a).mul(x).mul(x))
+                .add(constant(12).mul(big_b).mul(x))
+                .sub(big_a.mul(big_a));
+            let x = RingElement::new(
+                x0,
+                zero.sub(value.epsilon_coeff().mul(derivative.inv())),
+            );
+
+            let d_squared = constant(0).sub(constant(9).mul(x));
+            let Some(d) = sqrt(d_squared) else {
+                continue;
+            };
+
+            let d_cubed = d.mul(d).mul(d);
+            let a = constant(0)
+                .sub(constant(243).mul(big_a).mul(d.inv()).add(d_cubed))
+                .mul(constant(216).inv());
+
+            let valid = a.mul(constant(27).mul(a).sub(d_cubed)).is_invertible();
+            if valid {
+                let hessian = TwistedHessianCurve::new(a, d);
+                if hessian.to_weierstrass() == *curve {
+                    return Some(hessian);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl<const Q: u64> Projective<Q> {
+    /// Map a point of `curve` to the isomorphic short Weierstrass curve
+    /// [`TwistedHessianCurve::to_weierstrass`]
+    pub fn to_weierstrass(&self, curve: &TwistedHessianCurve<Q>) -> WeierstrassPoint<Q> {
+        assert!(curve.contains(self), "Point must be on the curve");
+
+        let (c3, k) = map_constants(curve);
+        // T = dX + 3(Y + Z)
+        let t = curve
+            .d()
+            .mul(self.x())
+            .add(constant(3).mul(self.y().add(self.z())));
+
+        WeierstrassPoint::new(
+            c3.mul(self.x()).sub(k.mul(t)),
+            c3.mul(self.y().sub(self.z())),
+            t,
+        )
+    }
+
+    /// Map a point of [`TwistedHessianCurve::to_weierstrass`] back to `curve`
+    pub fn from_weierstrass(
+        point: &WeierstrassPoint<Q>,
+        curve: &TwistedHessianCurve<Q>,
+    ) -> Self {
+        assert!(
+            curve.to_weierstrass().contains(point),
+            "Point must be on the Weierstrass curve"
+        );
+
+        let (c3, k) = map_constants(curve);
+        let d = curve.d();
+        // E = X + kZ = c₃X_H and T = c₃Z = c₃T_H, so the result is scaled by 6c₃
+        let e = point.x().add(k.mul(point.z()));
+        let t = c3.mul(point.z());
+        let three_y = constant(3).mul(point.y());
+
+        Projective::new(
+            constant(6).mul(e),
+            t.sub(d.mul(e)).add(three_y),
+            t.sub(d.mul(e)).sub(three_y),
+        )
+    }
+}
+
+/// The constants c₃ = 4(d³ - 27a)/27 and k = d²/9 of the map to Weierstrass form
+fn map_constants<const Q: u64>(
+    curve: &TwistedHessianCurve<Q>,
+) -> (RingElement<Q>, RingElement<Q>) {
+    let (a, d) = (curve.a(), curve.d());
+    let d_squared = d.mul(d);
+
+    let c3 = constant(4)
+        .mul(d_squared.mul(d).sub(constant(27).mul(a)))
+        .mul(constant(27).inv());
+    let k = d_squared.mul(constant(9).inv());
+
+    (c3, k)
+}
+
+/// A square root of an element with invertible constant part, if there is one
+#[cfg(feature = "alloc")]
+pub(crate) fn sqrt<const Q: u64>(value: RingElement<Q>) -> Option<RingElement<Q>> {
+    let v0 = value.constant();
+    if v0.value() == 0 {
+        return None;
+    }
+
+    // (s₀ + s₁ε)² = s₀² + 2s₀s₁ε
+    let s0 = v0.sqrt()?;
+    let s1 = value.epsilon_coeff().mul(Fq::new(2).mul(s0).inv());
+
+    Some(RingElement::new(s0, s1))
+}
+
+fn constant<const Q: u64>(value: u64) -> RingElement<Q> {
+    RingElement::from_field(Fq::new(value))
+}
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::paper_curve_and_generator;
+
+    #[test]
+    fn to_weierstrass__maps_all_points_and_back() {
+        let (curve, generator) = paper_curve_and_generator();
+        let weierstrass = curve.to_weierstrass();
+
+        assert!(Projective::identity().to_weierstrass(&curve).is_identity());
+
+        // P has order 45 and generates every point of the curve
+        for k in 0..45u64 {
+            let point = generator.scalar_mul(k, curve.a());
+            let mapped = point.to_weierstrass(&curve);
+
+            assert!(weierstrass.contains(&mapped));
+            assert_eq!(Projective::from_weierstrass(&mapped, &curve), point);
+        }
+    }
+
+    #[test]
+    fn to_weierstrass__is_a_homomorphism_over_all_points() {
+        let (curve, generator) = paper_curve_and_generator();
+        let weierstrass = curve.to_weierstrass();
+        let points: Vec<_> = (0..45u64)
+            .map(|k| generator.scalar_mul(k, curve.a()))
+            .collect();
+
+        for p in &points {
+            let wp = p.to_weierstrass(&curve);
+            assert_eq!(
+                wp.double(&weierstrass),
+                p.double(curve.a()).to_weierstrass(&curve)
+            );
+            assert_eq!(wp.negate(), p.negate().to_weierstrass(&curve));
+
+            for q in &points {
+                let wq = q.to_weierstrass(&curve);
+                let sum = weierstrass.add(&wp, &wq);
+
+                assert!(weierstrass.contains(&sum));
+                assert_eq!(sum, curve.add(p, q).to_weierstrass(&curve));
+            }
+        }
+    }
+
+    #[test]
+    fn to_weierstrass__is_a_homomorphism_proptest() {
+        type F11 = Fq<11>;
+
+        let a = RingElement::new(F11::new(1), F11::new(2)); // 1+2ε
+        let d = RingElement::new(F11::new(2), F11::new(1)); // 2+ε
+        let curve = TwistedHessianCurve::new(a, d);
+        let weierstrass = curve.to_weierstrass();
+        let p = Projective::new(
+            RingElement::from_field(F11::new(1)),
+            RingElement::new(F11::new(7), F11::new(6)),
+            RingElement::new(F11::new(4), F11::new(6)),
+        );
+        let wp = p.to_weierstrass(&curve);
+
+        proptest::proptest!(|(k1 in 0u64..10_000, k2 in 0u64..10_000)| {
+            let w1 = wp.scalar_mul(k1, &weierstrass);
+            let w2 = wp.scalar_mul(k2, &weierstrass);
+
+            assert_eq!(w1, p.scalar_mul(k1, a).to_weierstrass(&curve));
+            assert_eq!(
+                w1.add(&w2, &weierstrass),
+                p.scalar_mul(k1.saturating_add(k2), a).to_weierstrass(&curve)
+            );
+            assert_eq!(
+                w1.to_jacobian().add(&w2.to_jacobian(), &weierstrass).to_projective(),
+                w1.add(&w2, &weierstrass)
+            );
+        });
+    }
+
+    #[test]
+    fn jacobian__matches_projective_over_all_points() {
+        let (curve, generator) = paper_curve_and_generator();
+        let weierstrass = curve.to_weierstrass();
+        let points: Vec<_> = (0..45u64)
+            .map(|k| generator.scalar_mul(k, curve.a()).to_weierstrass(&curve))
+            .collect();
+
+        for p in &points {
+            let jp = p.to_jacobian();
+            assert_eq!(jp.to_projective(), *p);
+            assert_eq!(
+                jp.double(&weierstrass).to_projective(),
+                p.double(&weierstrass)
+            );
+            assert_eq!(jp.negate().to_projective(), p.negate());
+
+            for q in &points {
+                let sum = jp.add(&q.to_jacobian(), &weierstrass);
+                assert_eq!(sum.to_projective(), p.add(q, &weierstrass));
+            }
+        }
+
+        assert!(JacobianPoint::<5>::identity().is_identity());
+        assert_eq!(
+            points[1].to_jacobian().scalar_mul(45u64, &weierstrass),
+            JacobianPoint::identity()
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn from_weierstrass__round_trips() {
+        let (curve, generator) = paper_curve_and_generator();
+        let weierstrass = curve.to_weierstrass();
+
+        let hessian = TwistedHessianCurve::from_weierstrass(&weierstrass).unwrap();
+        assert_eq!(hessian.to_weierstrass(), weierstrass);
+
+        // the two Hessian curves are isomorphic through the Weierstrass model
+        let mapped =
+            Projective::from_weierstrass(&generator.to_weierstrass(&curve), &hessian);
+        assert!(hessian.contains(&mapped));
+        assert!(mapped.scalar_mul(45u64, hessian.a()).is_identity());
+        assert!(!mapped.scalar_mul(9u64, hessian.a()).is_identity());
+        assert!(!mapped.scalar_mul(15u64, hessian.a()).is_identity());
+    }
+
+    #[test]
+    fn to_affine__kats() {
+        let (curve, generator) = paper_curve_and_generator();
+        let point = generator.to_weierstrass(&curve);
+
+        let (x, y) = point.to_affine().unwrap();
+        assert_eq!(WeierstrassPoint::from_affine(x, y), point);
+        assert_eq!(WeierstrassPoint::<5>::identity().to_affine(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "4A³ + 27B² must be invertible for a valid curve")]
+    fn new__should_panic_when_curve_is_singular() {
+        type F5 = Fq<5>;
+
+        // y² = x³ + 2x + 3 has discriminant 4·8 + 27·9 = 275 ≡ 0 mod 5
+        WeierstrassCurve::new(
+            RingElement::new(F5::new(2), F5::new(1)),
+            RingElement::from_field(F5::new(3)),
+        );
+    }
+}
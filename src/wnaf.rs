@@ -0,0 +1,115 @@
+//! Width-w non-adjacent form (wNAF) recoding of scalars
+
+use crate::scalar::Scalar;
+
+/// Maximum number of wNAF digits of a [`Scalar`], one more than its bit length
+pub const MAX_DIGITS: usize = Scalar::BITS as usize + 1;
+
+/// Smallest supported window width
+pub const MIN_WIDTH: u32 = 2;
+
+/// Largest supported window width, keeping digits within an `i8`
+pub const MAX_WIDTH: u32 = 8;
+
+/// Signed-digit recoding of a scalar where every non-zero digit is odd, smaller than
+/// 2^(w-1) in absolute value, and followed by at least w - 1 zeros
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Wnaf {
+    digits: [i8; MAX_DIGITS],
+    len: usize,
+}
+
+impl Wnaf {
+    /// Recode a scalar with window width `width`
+    pub fn new(scalar: &Scalar, width: u32) -> Self {
+        assert!(
+            (MIN_WIDTH..=MAX_WIDTH).contains(&width),
+            "wNAF width must be between 2 and 8"
+        );
+
+        let window = 1u64 << width;
+        let half_window = window >> 1;
+
+        let mut digits = [0i8; MAX_DIGITS];
+        let mut len = 0;
+        // instead of subtracting negative digits from the scalar, carry a one into the
+        // next window; a final carry lands in the extra digit past the top bit
+        let mut carry = 0u64;
+        let mut pos = 0u32;
+
+        while pos <= Scalar::BITS {
+            let value = scalar.bits_at(pos, width).wrapping_add(carry);
+            if value & 1 == 0 {
+                pos = pos.saturating_add(1);
+                continue;
+            }
+
+            let index = pos as usize;
+            digits[index] = if value >= half_window {
+                carry = 1;
+                i8::try_from(i128::from(value).wrapping_sub(i128::from(window)))
+                    .expect("digit fits in the window")
+            } else {
+                carry = 0;
+                i8::try_from(value).expect("digit fits in the window")
+            };
+            len = index.saturating_add(1);
+            pos = pos.saturating_add(width);
+        }
+
+        Wnaf { digits, len }
+    }
+
+    /// Get the digits, least significant first
+    pub fn digits(&self) -> &[i8] {
+        &self.digits[..self.len]
+    }
+}
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::proptest;
+
+    #[test]
+    fn new__kats() {
+        // 7 = 8 - 1
+        assert_eq!(Wnaf::new(&7u64.into(), 2).digits(), &[-1, 0, 0, 1]);
+        // 7 is a single digit once the window holds it
+        assert_eq!(Wnaf::new(&7u64.into(), 4).digits(), &[7]);
+        assert!(Wnaf::new(&Scalar::ZERO, 4).digits().is_empty());
+
+        // 2^256 - 1 = 2^256 - 2^0, the carry lands in the extra top digit
+        let digits = Wnaf::new(&Scalar::MAX, 2);
+        assert_eq!(digits.digits().len(), MAX_DIGITS);
+        assert_eq!(digits.digits()[0], -1);
+        assert_eq!(digits.digits()[MAX_DIGITS - 1], 1);
+        assert!(digits.digits()[1..MAX_DIGITS - 1].iter().all(|d| *d == 0));
+    }
+
+    #[test]
+    fn new__proptest() {
+        proptest!(|(scalar: u64, width in MIN_WIDTH..=MAX_WIDTH)| {
+            let wnaf = Wnaf::new(&scalar.into(), width);
+            let digits = wnaf.digits();
+
+            let value = digits
+                .iter()
+                .rev()
+                .fold(0i128, |acc, d| (acc << 1).wrapping_add(i128::from(*d)));
+            assert_eq!(value, i128::from(scalar));
+
+            for (i, d) in digits.iter().enumerate() {
+                if *d != 0 {
+                    assert_eq!(d.rem_euclid(2), 1, "digits must be odd");
+                    assert!(i32::from(d.abs()) < (1 << width) >> 1);
+
+                    // the next w - 1 digits are zero
+                    let following = digits[i..].iter().take(width as usize).skip(1);
+                    assert!(following.into_iter().all(|d| *d == 0));
+                }
+            }
+        });
+    }
+}